@@ -1,15 +1,33 @@
-use std::env;
+mod export;
+mod forecast;
+mod rates;
+mod recurring;
+mod store;
+
+use std::collections::BTreeMap;
 use std::fmt::Display;
-use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
 use std::process::exit;
 
+use chrono::{Local, NaiveDate};
+use clap::{Parser, Subcommand};
+
+use crate::exercises::money_with_enums::{Currency, MonetaryAmount};
+use export::{OutputFormat, export};
+use forecast::forecast;
+use rates::RateTable;
+use recurring::{RecurringRule, Schedule};
+use store::{BudgetStore, Event, FileStore, JournalStore, StoreKind};
+
 const DEPOSIT: &str = "DEPOSIT";
 const WITHDRAW: &str = "WITHDRAW";
 const SEPARATOR: &str = ";";
-const FIELDS_COUNT: usize = 3;
+const MIN_FIELDS_COUNT: usize = 3;
+const FIELDS_COUNT: usize = 6;
 const FILE_NAME: &str = "budget.csv";
+const JOURNAL_FILE_NAME: &str = "budget.journal";
 
+#[derive(Clone, Copy, PartialEq)]
 enum OperationType {
     Deposit,
     Withdraw,
@@ -36,108 +54,359 @@ impl TryFrom<&str> for OperationType {
     }
 }
 
+#[derive(Clone)]
 struct Operation {
-    amount: f64,
+    amount: MonetaryAmount,
     description: String,
     operation_type: OperationType,
+    date: NaiveDate,
+    /// Index of the recurring rule that produced the operation, `None` for entered ones
+    rule: Option<usize>,
+}
+
+impl Operation {
+    fn signed_amount(&self) -> MonetaryAmount {
+        signed(&self.amount, self.operation_type)
+    }
+}
+
+fn signed(amount: &MonetaryAmount, operation_type: OperationType) -> MonetaryAmount {
+    match operation_type {
+        OperationType::Deposit => amount.clone(),
+        OperationType::Withdraw => MonetaryAmount::new(-amount.value(), amount.currency()),
+    }
 }
 
 impl Display for Operation {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "{}{}{}{}{}",
-            self.amount, SEPARATOR, self.description, SEPARATOR, self.operation_type
-        )
+            "{}{}{}{}{}{}{}{}{}",
+            self.amount.value(),
+            SEPARATOR,
+            self.description,
+            SEPARATOR,
+            self.operation_type,
+            SEPARATOR,
+            self.date,
+            SEPARATOR,
+            self.amount.currency()
+        )?;
+        match self.rule {
+            Some(rule) => write!(f, "{SEPARATOR}{rule}"),
+            None => Ok(()),
+        }
     }
 }
 
 impl TryFrom<&str> for Operation {
     type Error = String;
 
+    // Entries written before operations were dated have no date (today is used) and entries
+    // written before multi-currency support have no currency (PLN is used). Only operations
+    // produced by a recurring rule have the rule index, older synced ones count as entered
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let fields: Vec<&str> = value.split(SEPARATOR).collect();
-        if fields.len() != FIELDS_COUNT {
+        if !(MIN_FIELDS_COUNT..=FIELDS_COUNT).contains(&fields.len()) {
             return Err(format!("Invalid number of fields, expected: {value}"));
         }
         let amount: f64 = fields[0].parse().map_err(|_| "Invalid amount")?;
         let description = fields[1].to_string();
         let operation_type: OperationType = fields[2].try_into()?;
+        let date = match fields.get(3) {
+            Some(date) => date.parse().map_err(|_| format!("Invalid date: {date}"))?,
+            None => today(),
+        };
+        let currency = match fields.get(4) {
+            Some(currency) => Currency::try_from(*currency)?,
+            None => Currency::default(),
+        };
+        let rule = match fields.get(5) {
+            Some(rule) => Some(rule.parse().map_err(|_| format!("Invalid rule: {rule}"))?),
+            None => None,
+        };
         let operation = Operation {
-            amount,
+            amount: MonetaryAmount::new(amount, &currency),
             description,
             operation_type,
+            date,
+            rule,
         };
         Ok(operation)
     }
 }
 
-fn get_args() -> Vec<String> {
-    env::args().skip(1).collect()
+#[derive(Default)]
+struct Budget {
+    operations: Vec<Operation>,
+    recurring: Vec<RecurringRule>,
+    rates: RateTable,
 }
 
-fn display_summary(operations: &Vec<Operation>) {
-    let total_balance =
-        operations
+impl Budget {
+    fn balance(&self) -> Result<f64, String> {
+        self.operations
             .iter()
-            .fold(0.0, |acc, operation| match operation.operation_type {
-                OperationType::Deposit => acc + operation.amount,
-                OperationType::Withdraw => acc - operation.amount,
-            });
+            .map(|operation| self.rates.to_base(&operation.signed_amount()))
+            .map(|amount| amount.map(|amount| amount.value()))
+            .sum()
+    }
+
+    fn subtotals(&self) -> Result<Vec<MonetaryAmount>, String> {
+        let mut subtotals: BTreeMap<Currency, MonetaryAmount> = BTreeMap::new();
+        for operation in &self.operations {
+            let amount = operation.signed_amount();
+            let subtotal = match subtotals.remove(amount.currency()) {
+                Some(subtotal) => (subtotal + amount)?,
+                None => amount,
+            };
+            subtotals.insert(subtotal.currency().clone(), subtotal);
+        }
+        Ok(subtotals.into_values().collect())
+    }
+
+    fn sync(&mut self, until: NaiveDate) {
+        let materialized: Vec<Operation> = self
+            .recurring
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(index, rule)| rule.materialize(until, index))
+            .collect();
+        self.operations.extend(materialized);
+        self.operations.sort_by_key(|operation| operation.date);
+    }
+
+    fn apply(&mut self, event: &Event) -> Result<(), String> {
+        match event {
+            Event::Add(operation) => self.operations.push(operation.clone()),
+            Event::Recur(rule) => self.recurring.push(rule.clone()),
+            Event::Base(currency) => self.rates.set_base(currency.clone()),
+            Event::Rate(currency, rate) => self.rates.set_rate(currency.clone(), *rate)?,
+            Event::Sync(until) => self.sync(*until),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Parser)]
+#[command(
+    name = "budget",
+    about = "Household budget",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// New operation given as: amount description DEPOSIT|WITHDRAW
+    #[arg(num_args = 3, value_names = ["AMOUNT", "DESCRIPTION", "TYPE"])]
+    entry: Vec<String>,
+
+    /// Currency of the new operation, the base currency when omitted
+    #[arg(short, long)]
+    currency: Option<String>,
+
+    /// Storage backend, the journal keeps history and supports undo
+    #[arg(long, value_enum, global = true, default_value = "file")]
+    store: StoreKind,
+}
 
-    operations
+#[derive(Subcommand)]
+enum Command {
+    /// Add a recurring operation, e.g. --every monthly:1 or --every biweekly
+    Recur {
+        amount: f64,
+        description: String,
+        operation_type: String,
+        #[arg(long)]
+        every: String,
+        /// First occurrence, today when omitted
+        #[arg(long)]
+        start: Option<NaiveDate>,
+        #[arg(short, long)]
+        currency: Option<String>,
+    },
+    /// Set the base currency used for totals and forecasts, stored rates are converted to it
+    Base { currency: String },
+    /// Set the exchange rate as the value of one unit of a currency in the base currency
+    Rate { currency: String, rate: f64 },
+    /// Materialize recurring operations due up to today
+    Sync,
+    /// Project the balance forward from recurring operations and history averages
+    Forecast {
+        #[arg(long, default_value_t = 6, value_parser = clap::value_parser!(u32).range(1..))]
+        months: u32,
+    },
+    /// Export operations and summaries as a report
+    Export {
+        #[arg(short, long, value_enum)]
+        format: OutputFormat,
+        /// Output file, standard output when omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// List recorded changes (journal store only)
+    History,
+    /// Revert the most recent change (journal store only)
+    Undo,
+    /// Drop undone entries from the journal
+    Compact,
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+fn display_summary(budget: &Budget) {
+    budget
+        .operations
         .iter()
         .for_each(|operation| println!("{operation}"));
     println!("-----------------------------------------------------------");
-    println!("Total amount: {total_balance}");
+    match budget.subtotals() {
+        Ok(subtotals) => subtotals
+            .iter()
+            .for_each(|subtotal| println!("Subtotal {}: {subtotal}", subtotal.currency())),
+        Err(message) => println!("{message}"),
+    }
+    match budget.balance() {
+        Ok(balance) => println!("Total amount: {balance:.2} {}", budget.rates.base),
+        Err(message) => println!("Total amount unavailable: {message}"),
+    }
 }
 
-fn save(operations: &Vec<Operation>) {
-    let mut file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .open(FILE_NAME)
-        .expect("Could not open file");
-    operations
-        .iter()
-        .for_each(|operation| writeln!(file, "{operation}").expect("Could not write to file"));
+fn display_forecast(budget: &Budget, months: u32) {
+    let projections =
+        forecast(budget, today(), months).unwrap_or_else(|message| exit_with(message));
+    println!("Forecast in {}", budget.rates.base);
+    println!(
+        "{:<10} {:>12} {:>12} {:>12}",
+        "Month", "Recurring", "Average", "Balance"
+    );
+    println!("-----------------------------------------------------------");
+    for projection in projections {
+        println!(
+            "{:<10} {:>12.2} {:>12.2} {:>12.2}",
+            projection.month.format("%Y-%m"),
+            projection.recurring,
+            projection.average,
+            projection.balance
+        );
+    }
 }
 
-fn load() -> Result<Vec<Operation>, String> {
-    let file = File::open(FILE_NAME).expect("Could not open file");
-    let reader = BufReader::new(file);
-    let mut operations: Vec<Operation> = Vec::new();
-    for line in reader.lines() {
-        let line = line.expect("Could not read line from file");
-        let operation = line.as_str().try_into()?;
-        operations.push(operation);
+fn open_store(kind: StoreKind) -> Box<dyn BudgetStore> {
+    match kind {
+        StoreKind::File => Box::new(FileStore::new(FILE_NAME)),
+        StoreKind::Journal => Box::new(JournalStore::new(JOURNAL_FILE_NAME)),
     }
-    Ok(operations)
 }
 
-pub fn run() {
-    let args = get_args();
+fn exit_with(message: String) -> ! {
+    println!("{message}");
+    exit(0);
+}
 
-    if !args.is_empty() && args.len() != 3 {
-        println!("Invalid number of arguments, expected 2");
-        exit(0);
+fn parse_currency(currency: Option<String>, budget: &Budget) -> Currency {
+    match currency {
+        Some(currency) => {
+            Currency::try_from(currency.as_str()).unwrap_or_else(|message| exit_with(message))
+        }
+        None => budget.rates.base.clone(),
     }
+}
 
-    let mut operations = load().unwrap_or(Vec::new());
+pub fn run() {
+    let cli = Cli::parse();
+    let mut store = open_store(cli.store);
+    let budget = store.load().unwrap_or_else(|message| exit_with(message));
+    let mut record = |event: Event| {
+        store
+            .record(event)
+            .unwrap_or_else(|message| exit_with(message))
+    };
 
-    if args.len() == 3 {
-        let entry = args.join(SEPARATOR);
-        match Operation::try_from(entry.as_str()) {
-            Ok(operation) => {
-                operations.push(operation);
-                save(&operations);
+    match cli.command {
+        Some(Command::Recur {
+            amount,
+            description,
+            operation_type,
+            every,
+            start,
+            currency,
+        }) => {
+            let operation_type = OperationType::try_from(operation_type.as_str())
+                .unwrap_or_else(|message| exit_with(message));
+            let schedule =
+                Schedule::try_from(every.as_str()).unwrap_or_else(|message| exit_with(message));
+            let currency = parse_currency(currency, &budget);
+            let rule = RecurringRule::new(
+                MonetaryAmount::new(amount, &currency),
+                description,
+                operation_type,
+                schedule,
+                start.unwrap_or_else(today),
+            );
+            println!("Next occurrence: {}", rule.next);
+            record(Event::Recur(rule));
+        }
+        Some(Command::Sync) => {
+            let synced = record(Event::Sync(today()));
+            let count = synced.operations.len() - budget.operations.len();
+            println!("Materialized operations: {count}");
+            display_summary(&synced);
+        }
+        Some(Command::Base { currency }) => {
+            let currency =
+                Currency::try_from(currency.as_str()).unwrap_or_else(|message| exit_with(message));
+            display_summary(&record(Event::Base(currency)));
+        }
+        Some(Command::Rate { currency, rate }) => {
+            let currency =
+                Currency::try_from(currency.as_str()).unwrap_or_else(|message| exit_with(message));
+            display_summary(&record(Event::Rate(currency, rate)));
+        }
+        Some(Command::Forecast { months }) => display_forecast(&budget, months),
+        Some(Command::Export { format, output }) => {
+            let report = export(&budget, format).unwrap_or_else(|message| exit_with(message));
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, report)
+                        .unwrap_or_else(|e| exit_with(format!("Could not write to file: {e}")));
+                    println!("Report saved to {}", path.display());
+                }
+                None => print!("{report}"),
             }
-            Err(message) => {
-                println!("{}", message);
-                exit(0);
+        }
+        Some(Command::History) => {
+            let events = store.history().unwrap_or_else(|message| exit_with(message));
+            for (index, event) in events.iter().enumerate() {
+                println!("{:4}. {event}", index + 1);
+            }
+        }
+        Some(Command::Undo) => {
+            let event = store.undo().unwrap_or_else(|message| exit_with(message));
+            println!("Reverted: {event}");
+        }
+        Some(Command::Compact) => {
+            let removed = store.compact().unwrap_or_else(|message| exit_with(message));
+            println!("Removed entries: {removed}");
+        }
+        None => {
+            if cli.entry.is_empty() {
+                display_summary(&budget);
+            } else {
+                let currency = parse_currency(cli.currency, &budget);
+                let entry = format!(
+                    "{}{SEPARATOR}{}{SEPARATOR}{currency}",
+                    cli.entry.join(SEPARATOR),
+                    today()
+                );
+                match Operation::try_from(entry.as_str()) {
+                    Ok(operation) => display_summary(&record(Event::Add(operation))),
+                    Err(message) => exit_with(message),
+                }
             }
         }
     }
-
-    display_summary(&operations);
 }
@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::exercises::money_with_enums::{Currency, MonetaryAmount};
+use crate::exercises::utils::{csv_string, escape_json, escape_markdown};
+
+use super::{Budget, Operation};
+
+const CHART_WIDTH: usize = 600;
+const CHART_HEIGHT: usize = 200;
+const BAR_GAP: usize = 4;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum OutputFormat {
+    Json,
+    Csv,
+    Md,
+    Html,
+}
+
+struct Report<'a> {
+    operations: &'a [Operation],
+    subtotals: Vec<MonetaryAmount>,
+    base: &'a Currency,
+    total: f64,
+    monthly: Vec<(NaiveDate, f64)>,
+}
+
+impl<'a> Report<'a> {
+    fn new(budget: &'a Budget) -> Result<Self, String> {
+        let mut monthly: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+        for operation in &budget.operations {
+            let month = operation.date.with_day(1).expect("First day always exists");
+            *monthly.entry(month).or_default() +=
+                budget.rates.to_base(&operation.signed_amount())?.value();
+        }
+        Ok(Report {
+            operations: &budget.operations,
+            subtotals: budget.subtotals()?,
+            base: &budget.rates.base,
+            total: budget.balance()?,
+            monthly: monthly.into_iter().collect(),
+        })
+    }
+}
+
+pub fn export(budget: &Budget, format: OutputFormat) -> Result<String, String> {
+    let report = Report::new(budget)?;
+    let output = match format {
+        OutputFormat::Json => to_json(&report),
+        OutputFormat::Csv => to_csv(&report)?,
+        OutputFormat::Md => to_markdown(&report),
+        OutputFormat::Html => to_html(&report),
+    };
+    Ok(output)
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn to_json(report: &Report) -> String {
+    let operations: Vec<String> = report
+        .operations
+        .iter()
+        .map(|operation| {
+            format!(
+                "    {{\"date\": \"{}\", \"type\": \"{}\", \"description\": \"{}\", \"amount\": {:.2}, \"currency\": \"{}\"}}",
+                operation.date,
+                operation.operation_type,
+                escape_json(&operation.description),
+                operation.amount.value(),
+                operation.amount.currency()
+            )
+        })
+        .collect();
+    let subtotals: Vec<String> = report
+        .subtotals
+        .iter()
+        .map(|subtotal| format!("\"{}\": {:.2}", subtotal.currency(), subtotal.value()))
+        .collect();
+    let monthly: Vec<String> = report
+        .monthly
+        .iter()
+        .map(|(month, net)| format!("\"{}\": {net:.2}", month.format("%Y-%m")))
+        .collect();
+    format!(
+        "{{\n  \"operations\": [\n{}\n  ],\n  \"subtotals\": {{{}}},\n  \"monthly_net\": {{{}}},\n  \"total\": {{\"amount\": {:.2}, \"currency\": \"{}\"}}\n}}\n",
+        operations.join(",\n"),
+        subtotals.join(", "),
+        monthly.join(", "),
+        report.total,
+        report.base
+    )
+}
+
+fn to_csv(report: &Report) -> Result<String, String> {
+    let mut records = Vec::new();
+    records.push(vec![
+        "date".into(),
+        "type".into(),
+        "description".into(),
+        "amount".into(),
+        "currency".into(),
+    ]);
+    for operation in report.operations {
+        records.push(vec![
+            operation.date.to_string(),
+            operation.operation_type.to_string(),
+            operation.description.clone(),
+            format!("{:.2}", operation.amount.value()),
+            operation.amount.currency().to_string(),
+        ]);
+    }
+    for subtotal in &report.subtotals {
+        records.push(vec![
+            String::new(),
+            "SUBTOTAL".into(),
+            String::new(),
+            format!("{:.2}", subtotal.value()),
+            subtotal.currency().to_string(),
+        ]);
+    }
+    records.push(vec![
+        String::new(),
+        "TOTAL".into(),
+        String::new(),
+        format!("{:.2}", report.total),
+        report.base.to_string(),
+    ]);
+    csv_string(&records)
+}
+
+fn to_markdown(report: &Report) -> String {
+    let mut output = String::from("# Budget report\n\n");
+    output.push_str("| Date | Type | Description | Amount | Currency |\n");
+    output.push_str("|------|------|-------------|-------:|----------|\n");
+    for operation in report.operations {
+        let _ = writeln!(
+            output,
+            "| {} | {} | {} | {:.2} | {} |",
+            operation.date,
+            operation.operation_type,
+            escape_markdown(&operation.description),
+            operation.amount.value(),
+            operation.amount.currency()
+        );
+    }
+    output.push_str("\n## Summary\n\n");
+    for subtotal in &report.subtotals {
+        let _ = writeln!(output, "- Subtotal {}: {subtotal}", subtotal.currency());
+    }
+    let _ = writeln!(output, "- **Total: {:.2} {}**", report.total, report.base);
+    output
+}
+
+fn to_svg_chart(monthly: &[(NaiveDate, f64)]) -> String {
+    if monthly.is_empty() {
+        return String::new();
+    }
+    let max = monthly
+        .iter()
+        .map(|(_, net)| net.abs())
+        .fold(0.0, f64::max)
+        .max(f64::EPSILON);
+    let middle = CHART_HEIGHT as f64 / 2.0;
+    let bar_width = (CHART_WIDTH / monthly.len()).saturating_sub(BAR_GAP).max(1);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{}\">\n",
+        CHART_HEIGHT + 20
+    );
+    let _ = writeln!(
+        svg,
+        "  <line x1=\"0\" y1=\"{middle}\" x2=\"{CHART_WIDTH}\" y2=\"{middle}\" stroke=\"#999\"/>"
+    );
+    for (index, (month, net)) in monthly.iter().enumerate() {
+        let height = net.abs() / max * (middle - 1.0);
+        let y = if *net >= 0.0 { middle - height } else { middle };
+        let x = index * (bar_width + BAR_GAP);
+        let color = if *net >= 0.0 { "#4caf50" } else { "#e53935" };
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{x}\" y=\"{y:.1}\" width=\"{bar_width}\" height=\"{height:.1}\" fill=\"{color}\"><title>{}: {net:.2}</title></rect>",
+            month.format("%Y-%m")
+        );
+        let _ = writeln!(
+            svg,
+            "  <text x=\"{x}\" y=\"{}\" font-size=\"10\">{}</text>",
+            CHART_HEIGHT + 15,
+            month.format("%Y-%m")
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn to_html(report: &Report) -> String {
+    let mut output = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Budget report</title>\n</head>\n<body>\n<h1>Budget report</h1>\n",
+    );
+    output.push_str("<table>\n<tr><th>Date</th><th>Type</th><th>Description</th><th>Amount</th><th>Currency</th></tr>\n");
+    for operation in report.operations {
+        let _ = writeln!(
+            output,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td><td>{}</td></tr>",
+            operation.date,
+            operation.operation_type,
+            escape_html(&operation.description),
+            operation.amount.value(),
+            operation.amount.currency()
+        );
+    }
+    output.push_str("</table>\n<h2>Summary</h2>\n<ul>\n");
+    for subtotal in &report.subtotals {
+        let _ = writeln!(
+            output,
+            "<li>Subtotal {}: {subtotal}</li>",
+            subtotal.currency()
+        );
+    }
+    let _ = writeln!(
+        output,
+        "<li><strong>Total: {:.2} {}</strong></li>\n</ul>",
+        report.total, report.base
+    );
+    let _ = writeln!(output, "<h2>Monthly net ({})</h2>", report.base);
+    output.push_str(&to_svg_chart(&report.monthly));
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exercises::budget::OperationType;
+
+    fn budget(description: &str) -> Budget {
+        let operation = |value, description: &str, operation_type, day: &str| Operation {
+            amount: MonetaryAmount::new(value, &Currency::Pln),
+            description: description.to_string(),
+            operation_type,
+            date: day.parse().unwrap(),
+            rule: None,
+        };
+        Budget {
+            operations: vec![
+                operation(100.0, "Salary", OperationType::Deposit, "2026-01-10"),
+                operation(30.5, description, OperationType::Withdraw, "2026-02-03"),
+            ],
+            ..Budget::default()
+        }
+    }
+
+    #[test]
+    fn json_escapes_quotes_and_line_breaks() {
+        let output = export(&budget("Say \"hi\",\nback\\slash"), OutputFormat::Json).unwrap();
+
+        assert!(output.contains(
+            "\"type\": \"WITHDRAW\", \"description\": \"Say \\\"hi\\\",\\nback\\\\slash\", \"amount\": 30.50"
+        ));
+        assert!(output.contains("\"monthly_net\": {\"2026-01\": 100.00, \"2026-02\": -30.50}"));
+        assert!(output.contains("\"total\": {\"amount\": 69.50, \"currency\": \"PLN\"}"));
+    }
+
+    #[test]
+    fn csv_quotes_fields_with_separators() {
+        let output = export(&budget("Say \"hi\",\nback"), OutputFormat::Csv).unwrap();
+
+        assert_eq!(
+            output,
+            "date,type,description,amount,currency\n\
+             2026-01-10,DEPOSIT,Salary,100.00,PLN\n\
+             2026-02-03,WITHDRAW,\"Say \"\"hi\"\",\nback\",30.50,PLN\n\
+             ,SUBTOTAL,,69.50,PLN\n\
+             ,TOTAL,,69.50,PLN\n"
+        );
+    }
+
+    #[test]
+    fn markdown_escapes_table_separators() {
+        let output = export(&budget("Tea | <cake>"), OutputFormat::Md).unwrap();
+
+        assert!(output.contains("| 2026-02-03 | WITHDRAW | Tea \\| <cake> | 30.50 | PLN |\n"));
+        assert!(output.ends_with("- **Total: 69.50 PLN**\n"));
+    }
+
+    #[test]
+    fn html_escapes_markup() {
+        let output = export(&budget("Tea | <cake> & \"jam\""), OutputFormat::Html).unwrap();
+
+        assert!(output.contains("<td>Tea | &lt;cake&gt; &amp; &quot;jam&quot;</td>"));
+        assert!(!output.contains("<cake>"));
+        assert_eq!(output.matches("<rect ").count(), 2);
+    }
+}
@@ -0,0 +1,158 @@
+use chrono::{Datelike, Months, NaiveDate};
+
+use super::Budget;
+
+pub struct MonthProjection {
+    pub month: NaiveDate,
+    pub recurring: f64,
+    pub average: f64,
+    pub balance: f64,
+}
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("First day always exists")
+}
+
+// Months from the month of `from` to the month of `to`, 0 when both are in the same month
+fn months_between(from: NaiveDate, to: NaiveDate) -> i32 {
+    (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32
+}
+
+// Average monthly net of entered operations over every calendar month from the first operation
+// to the current one, so months without operations lower the average
+fn history_average(budget: &Budget, today: NaiveDate) -> Result<f64, String> {
+    let Some(first) = budget
+        .operations
+        .iter()
+        .map(|operation| operation.date)
+        .min()
+    else {
+        return Ok(0.0);
+    };
+    let mut total = 0.0;
+    for operation in budget
+        .operations
+        .iter()
+        .filter(|operation| operation.rule.is_none())
+    {
+        total += budget.rates.to_base(&operation.signed_amount())?.value();
+    }
+    let months = months_between(first, today).max(0) + 1;
+    Ok(total / months as f64)
+}
+
+pub fn forecast(
+    budget: &Budget,
+    today: NaiveDate,
+    months: u32,
+) -> Result<Vec<MonthProjection>, String> {
+    let first_month = month_start(today);
+    let horizon = first_month + Months::new(months);
+    let average = history_average(budget, today)?;
+
+    // Occurrences that were due before this month and not synced yet land in the first month
+    let mut recurring = vec![0.0; months as usize];
+    for rule in &budget.recurring {
+        let amount = budget.rates.to_base(&rule.signed_amount())?.value();
+        for date in rule.occurrences().take_while(|date| *date < horizon) {
+            let index = if date < first_month {
+                0
+            } else {
+                months_between(first_month, date) as usize
+            };
+            recurring[index] += amount;
+        }
+    }
+
+    let mut balance = budget.balance()?;
+    let projections = recurring
+        .into_iter()
+        .enumerate()
+        .map(|(index, recurring)| {
+            balance += recurring + average;
+            MonthProjection {
+                month: first_month + Months::new(index as u32),
+                recurring,
+                average,
+                balance,
+            }
+        })
+        .collect();
+    Ok(projections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exercises::budget::recurring::{RecurringRule, Schedule};
+    use crate::exercises::budget::{Operation, OperationType};
+    use crate::exercises::money_with_enums::{Currency, MonetaryAmount};
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn rent(start: &str) -> RecurringRule {
+        RecurringRule::new(
+            MonetaryAmount::new(1000.0, &Currency::Pln),
+            String::from("Rent"),
+            OperationType::Withdraw,
+            Schedule::Monthly(1),
+            date(start),
+        )
+    }
+
+    #[test]
+    fn overdue_occurrences_land_in_the_first_month() {
+        let budget = Budget {
+            recurring: vec![rent("2026-01-01")],
+            ..Budget::default()
+        };
+
+        let projections = forecast(&budget, date("2026-03-20"), 3).unwrap();
+
+        assert_eq!(
+            projections
+                .iter()
+                .map(|projection| projection.month)
+                .collect::<Vec<_>>(),
+            [date("2026-03-01"), date("2026-04-01"), date("2026-05-01")]
+        );
+        assert_eq!(projections[0].recurring, -3000.0);
+        assert_eq!(projections[1].recurring, -1000.0);
+        assert_eq!(projections[2].balance, -5000.0);
+    }
+
+    #[test]
+    fn history_average_skips_operations_produced_by_rules() {
+        let operation = |amount, description: &str, day, rule| Operation {
+            amount: MonetaryAmount::new(amount, &Currency::Pln),
+            description: description.to_string(),
+            operation_type: OperationType::Deposit,
+            date: date(day),
+            rule,
+        };
+        let budget = Budget {
+            operations: vec![
+                operation(300.0, "Salary", "2026-01-10", None),
+                operation(50.0, "Bonus", "2026-02-20", None),
+                operation(50.0, "Bonus", "2026-03-20", Some(0)),
+            ],
+            recurring: vec![RecurringRule::new(
+                MonetaryAmount::new(50.0, &Currency::Pln),
+                String::from("Bonus"),
+                OperationType::Deposit,
+                Schedule::Monthly(20),
+                date("2026-04-01"),
+            )],
+            ..Budget::default()
+        };
+
+        let projections = forecast(&budget, date("2026-04-01"), 1).unwrap();
+
+        // 350 entered over January to April, the months without entries count as well
+        assert_eq!(projections[0].average, 87.5);
+        assert_eq!(projections[0].recurring, 50.0);
+        assert_eq!(projections[0].balance, 537.5);
+    }
+}
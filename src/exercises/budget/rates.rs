@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use crate::exercises::money_with_enums::{Currency, MonetaryAmount};
+
+use super::SEPARATOR;
+
+pub const BASE: &str = "BASE";
+pub const RATE: &str = "RATE";
+
+// Rates are stored as the value of one unit of a currency in the base currency, e.g. RATE;EUR;4.3
+#[derive(Default)]
+pub struct RateTable {
+    pub base: Currency,
+    rates: BTreeMap<Currency, f64>,
+}
+
+impl RateTable {
+    pub fn set_rate(&mut self, currency: Currency, rate: f64) -> Result<(), String> {
+        if rate <= 0.0 {
+            return Err(format!("Exchange rate must be positive, got: {rate}"));
+        }
+        self.rates.insert(currency, rate);
+        Ok(())
+    }
+
+    /// Expresses the stored rates in the new base currency. Without a rate for the new base
+    /// they cannot be converted, so the table is cleared and the rates have to be set again
+    pub fn set_base(&mut self, currency: Currency) {
+        if currency == self.base {
+            return;
+        }
+        match self.rates.remove(&currency) {
+            Some(new_base_rate) => {
+                for rate in self.rates.values_mut() {
+                    *rate /= new_base_rate;
+                }
+                let old_base = std::mem::replace(&mut self.base, currency);
+                self.rates.insert(old_base, 1.0 / new_base_rate);
+            }
+            None => {
+                self.rates.clear();
+                self.base = currency;
+            }
+        }
+    }
+
+    pub fn to_base(&self, amount: &MonetaryAmount) -> Result<MonetaryAmount, String> {
+        if *amount.currency() == self.base {
+            return Ok(amount.clone());
+        }
+        let rate = self.rates.get(amount.currency()).ok_or_else(|| {
+            format!(
+                "No exchange rate for {} to {}",
+                amount.currency(),
+                self.base
+            )
+        })?;
+        Ok(MonetaryAmount::convert(amount, 1.0 / rate, &self.base))
+    }
+
+    pub fn parse_line(&mut self, line: &str) -> Result<(), String> {
+        let fields: Vec<&str> = line.split(SEPARATOR).collect();
+        match fields.as_slice() {
+            [BASE, currency] => {
+                self.base = Currency::try_from(*currency)?;
+                Ok(())
+            }
+            [RATE, currency, rate] => {
+                let rate: f64 = rate.parse().map_err(|_| format!("Invalid rate: {rate}"))?;
+                self.set_rate(Currency::try_from(*currency)?, rate)
+            }
+            _ => Err(format!("Invalid rate entry: {line}")),
+        }
+    }
+}
+
+impl Display for RateTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{BASE}{SEPARATOR}{}", self.base)?;
+        for (currency, rate) in &self.rates {
+            write!(f, "\n{RATE}{SEPARATOR}{currency}{SEPARATOR}{rate}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(lines: &[&str]) -> RateTable {
+        let mut table = RateTable::default();
+        for line in lines {
+            table.parse_line(line).unwrap();
+        }
+        table
+    }
+
+    fn in_base(table: &RateTable, value: f64, currency: Currency) -> Result<f64, String> {
+        table
+            .to_base(&MonetaryAmount::new(value, &currency))
+            .map(|amount| amount.value())
+    }
+
+    #[test]
+    fn converts_with_the_stored_rates() {
+        let table = table(&["BASE;PLN", "RATE;USD;4", "RATE;EUR;4.25"]);
+
+        assert_eq!(in_base(&table, 10.0, Currency::Usd), Ok(40.0));
+        assert_eq!(in_base(&table, 2.0, Currency::Eur), Ok(8.5));
+        assert_eq!(in_base(&table, 3.0, Currency::Pln), Ok(3.0));
+        assert_eq!(table.to_string(), "BASE;PLN\nRATE;EUR;4.25\nRATE;USD;4");
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        let mut table = RateTable::default();
+
+        assert!(table.parse_line("RATE;EUR;-1").is_err());
+        assert!(table.parse_line("RATE;EUR;abc").is_err());
+        assert!(table.parse_line("RATE;GBP;5").is_err());
+        assert!(table.parse_line("BASE").is_err());
+        assert_eq!(
+            in_base(&table, 1.0, Currency::Eur),
+            Err(String::from("No exchange rate for EUR to PLN"))
+        );
+    }
+
+    #[test]
+    fn changing_the_base_converts_the_rates() {
+        let mut table = table(&["BASE;PLN", "RATE;USD;4", "RATE;EUR;5"]);
+
+        table.set_base(Currency::Eur);
+
+        assert_eq!(table.base, Currency::Eur);
+        assert_eq!(in_base(&table, 10.0, Currency::Usd), Ok(8.0));
+        assert_eq!(in_base(&table, 10.0, Currency::Pln), Ok(2.0));
+        assert_eq!(in_base(&table, 10.0, Currency::Eur), Ok(10.0));
+    }
+
+    #[test]
+    fn changing_to_a_base_without_a_rate_clears_the_rates() {
+        let mut table = table(&["BASE;PLN", "RATE;USD;4"]);
+
+        table.set_base(Currency::Eur);
+
+        assert_eq!(table.to_string(), "BASE;EUR");
+        assert!(in_base(&table, 1.0, Currency::Usd).is_err());
+    }
+}
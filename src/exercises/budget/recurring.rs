@@ -0,0 +1,288 @@
+use std::fmt::Display;
+
+use chrono::{Datelike, Duration, Months, NaiveDate};
+
+use crate::exercises::money_with_enums::{Currency, MonetaryAmount};
+
+use super::{Operation, OperationType, SEPARATOR, signed};
+
+pub const RECURRING: &str = "RECURRING";
+const WEEKLY: &str = "WEEKLY";
+const BIWEEKLY: &str = "BIWEEKLY";
+const MONTHLY: &str = "MONTHLY";
+const DAY_SEPARATOR: &str = ":";
+const MIN_FIELDS_COUNT: usize = 6;
+const FIELDS_COUNT: usize = 7;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Schedule {
+    Weekly,
+    BiWeekly,
+    Monthly(u32),
+}
+
+impl Schedule {
+    fn first_on_or_after(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Schedule::Weekly | Schedule::BiWeekly => date,
+            Schedule::Monthly(day) => {
+                let candidate = day_in_month(date.year(), date.month(), *day);
+                if candidate >= date {
+                    candidate
+                } else {
+                    self.advance(candidate)
+                }
+            }
+        }
+    }
+
+    fn advance(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Schedule::Weekly => date + Duration::weeks(1),
+            Schedule::BiWeekly => date + Duration::weeks(2),
+            Schedule::Monthly(day) => {
+                let next_month =
+                    date.with_day(1).expect("First day always exists") + Months::new(1);
+                day_in_month(next_month.year(), next_month.month(), *day)
+            }
+        }
+    }
+}
+
+// Days past the end of a short month fall on its last day, e.g. "monthly:31" in February
+fn day_in_month(year: i32, month: u32, day: u32) -> NaiveDate {
+    (1..=day)
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .expect("Every month has a first day")
+}
+
+impl Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Schedule::Weekly => write!(f, "{WEEKLY}"),
+            Schedule::BiWeekly => write!(f, "{BIWEEKLY}"),
+            Schedule::Monthly(day) => write!(f, "{MONTHLY}{DAY_SEPARATOR}{day}"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Schedule {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let normalized = value.trim().to_uppercase();
+        match normalized.split_once(DAY_SEPARATOR) {
+            Some((MONTHLY, day)) => match day.parse() {
+                Ok(day @ 1..=31) => Ok(Schedule::Monthly(day)),
+                _ => Err(format!("Invalid day of month: {day}")),
+            },
+            None if normalized == WEEKLY => Ok(Schedule::Weekly),
+            None if normalized == BIWEEKLY => Ok(Schedule::BiWeekly),
+            _ => Err(format!("Unknown schedule: {value}")),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RecurringRule {
+    amount: MonetaryAmount,
+    description: String,
+    operation_type: OperationType,
+    schedule: Schedule,
+    pub next: NaiveDate,
+}
+
+impl RecurringRule {
+    pub fn new(
+        amount: MonetaryAmount,
+        description: String,
+        operation_type: OperationType,
+        schedule: Schedule,
+        start: NaiveDate,
+    ) -> Self {
+        RecurringRule {
+            amount,
+            description,
+            operation_type,
+            next: schedule.first_on_or_after(start),
+            schedule,
+        }
+    }
+
+    pub fn signed_amount(&self) -> MonetaryAmount {
+        signed(&self.amount, self.operation_type)
+    }
+
+    pub fn occurrences(&self) -> impl Iterator<Item = NaiveDate> {
+        let schedule = self.schedule;
+        std::iter::successors(Some(self.next), move |date| Some(schedule.advance(*date)))
+    }
+
+    /// Operations due up to `until`, marked as produced by the rule at `index` of the budget
+    pub fn materialize(&mut self, until: NaiveDate, index: usize) -> Vec<Operation> {
+        let dates: Vec<NaiveDate> = self
+            .occurrences()
+            .take_while(|date| *date <= until)
+            .collect();
+        if let Some(last) = dates.last() {
+            self.next = self.schedule.advance(*last);
+        }
+        dates
+            .into_iter()
+            .map(|date| Operation {
+                amount: self.amount.clone(),
+                description: self.description.clone(),
+                operation_type: self.operation_type,
+                date,
+                rule: Some(index),
+            })
+            .collect()
+    }
+}
+
+impl Display for RecurringRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{RECURRING}{SEPARATOR}{}{SEPARATOR}{}{SEPARATOR}{}{SEPARATOR}{}{SEPARATOR}{}{SEPARATOR}{}",
+            self.amount.value(),
+            self.description,
+            self.operation_type,
+            self.schedule,
+            self.next,
+            self.amount.currency()
+        )
+    }
+}
+
+impl TryFrom<&str> for RecurringRule {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let fields: Vec<&str> = value.split(SEPARATOR).collect();
+        if !(MIN_FIELDS_COUNT..=FIELDS_COUNT).contains(&fields.len()) || fields[0] != RECURRING {
+            return Err(format!("Invalid recurring entry: {value}"));
+        }
+        let amount: f64 = fields[1].parse().map_err(|_| "Invalid amount")?;
+        let description = fields[2].to_string();
+        let operation_type: OperationType = fields[3].try_into()?;
+        let schedule: Schedule = fields[4].try_into()?;
+        let next: NaiveDate = fields[5]
+            .parse()
+            .map_err(|_| format!("Invalid date: {}", fields[5]))?;
+        let currency = match fields.get(6) {
+            Some(currency) => Currency::try_from(*currency)?,
+            None => Currency::default(),
+        };
+        Ok(RecurringRule {
+            amount: MonetaryAmount::new(amount, &currency),
+            description,
+            operation_type,
+            schedule,
+            next,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exercises::budget::Budget;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn rule(schedule: Schedule, start: &str) -> RecurringRule {
+        RecurringRule::new(
+            MonetaryAmount::new(1000.0, &Currency::Pln),
+            String::from("Rent"),
+            OperationType::Withdraw,
+            schedule,
+            date(start),
+        )
+    }
+
+    fn first_occurrences(rule: &RecurringRule, count: usize) -> Vec<NaiveDate> {
+        rule.occurrences().take(count).collect()
+    }
+
+    #[test]
+    fn month_end_days_fall_on_the_last_day_of_short_months() {
+        let rule = rule(Schedule::Monthly(31), "2026-01-15");
+
+        assert_eq!(
+            first_occurrences(&rule, 4),
+            [
+                date("2026-01-31"),
+                date("2026-02-28"),
+                date("2026-03-31"),
+                date("2026-04-30")
+            ]
+        );
+    }
+
+    #[test]
+    fn leap_day_occurs_only_in_leap_years() {
+        let rule = rule(Schedule::Monthly(29), "2024-02-01");
+
+        assert_eq!(
+            rule.occurrences()
+                .filter(|date| date.month() == 2)
+                .take(2)
+                .collect::<Vec<_>>(),
+            [date("2024-02-29"), date("2025-02-28")]
+        );
+    }
+
+    #[test]
+    fn weekly_schedules_start_on_the_given_day() {
+        assert_eq!(
+            first_occurrences(&rule(Schedule::BiWeekly, "2026-03-25"), 3),
+            [date("2026-03-25"), date("2026-04-08"), date("2026-04-22")]
+        );
+        assert_eq!(
+            rule(Schedule::Monthly(10), "2026-03-11").next,
+            date("2026-04-10")
+        );
+    }
+
+    #[test]
+    fn materialize_produces_each_occurrence_once() {
+        let mut rule = rule(Schedule::Weekly, "2026-05-01");
+
+        let operations = rule.materialize(date("2026-05-15"), 2);
+
+        assert_eq!(operations.len(), 3);
+        assert!(operations.iter().all(|operation| operation.rule == Some(2)));
+        assert!(rule.materialize(date("2026-05-15"), 2).is_empty());
+        assert_eq!(rule.next, date("2026-05-22"));
+    }
+
+    #[test]
+    fn sync_twice_does_not_duplicate_operations() {
+        let mut budget = Budget {
+            recurring: vec![rule(Schedule::Monthly(1), "2026-01-01")],
+            ..Budget::default()
+        };
+
+        budget.sync(date("2026-03-15"));
+        budget.sync(date("2026-03-15"));
+
+        assert_eq!(budget.operations.len(), 3);
+        assert_eq!(budget.balance(), Ok(-3000.0));
+        let stored = budget.operations[0].to_string();
+        assert_eq!(stored, "1000;Rent;WITHDRAW;2026-01-01;PLN;0");
+        assert_eq!(Operation::try_from(stored.as_str()).unwrap().rule, Some(0));
+    }
+
+    #[test]
+    fn rule_round_trips_through_its_text_form() {
+        let text = "RECURRING;1000;Rent;WITHDRAW;MONTHLY:31;2026-02-28;EUR";
+        let rule = RecurringRule::try_from(text).unwrap();
+
+        assert_eq!(rule.to_string(), text);
+        assert!(Schedule::try_from("monthly:32").is_err());
+    }
+}
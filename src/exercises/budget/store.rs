@@ -0,0 +1,405 @@
+use std::fmt::Display;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+
+use crate::exercises::money_with_enums::Currency;
+
+use super::rates::{BASE, RATE};
+use super::recurring::{RECURRING, RecurringRule};
+use super::{Budget, Operation, SEPARATOR};
+
+const ADD: &str = "ADD";
+const SYNC: &str = "SYNC";
+const UNDO: &str = "UNDO";
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum StoreKind {
+    File,
+    Journal,
+}
+
+#[derive(Clone)]
+pub enum Event {
+    Add(Operation),
+    Recur(RecurringRule),
+    Base(Currency),
+    Rate(Currency, f64),
+    Sync(NaiveDate),
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Event::Add(operation) => write!(f, "{ADD}{SEPARATOR}{operation}"),
+            Event::Recur(rule) => write!(f, "{rule}"),
+            Event::Base(currency) => write!(f, "{BASE}{SEPARATOR}{currency}"),
+            Event::Rate(currency, rate) => {
+                write!(f, "{RATE}{SEPARATOR}{currency}{SEPARATOR}{rate}")
+            }
+            Event::Sync(until) => write!(f, "{SYNC}{SEPARATOR}{until}"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Event {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (tag, payload) = value
+            .split_once(SEPARATOR)
+            .ok_or_else(|| format!("Invalid event: {value}"))?;
+        match tag {
+            ADD => Ok(Event::Add(payload.try_into()?)),
+            RECURRING => Ok(Event::Recur(value.try_into()?)),
+            BASE => Ok(Event::Base(Currency::try_from(payload)?)),
+            RATE => {
+                let (currency, rate) = payload
+                    .split_once(SEPARATOR)
+                    .ok_or_else(|| format!("Invalid event: {value}"))?;
+                let rate = rate.parse().map_err(|_| format!("Invalid rate: {rate}"))?;
+                Ok(Event::Rate(Currency::try_from(currency)?, rate))
+            }
+            SYNC => Ok(Event::Sync(
+                payload
+                    .parse()
+                    .map_err(|_| format!("Invalid date: {payload}"))?,
+            )),
+            _ => Err(format!("Unknown event: {value}")),
+        }
+    }
+}
+
+pub trait BudgetStore {
+    fn load(&self) -> Result<Budget, String>;
+
+    /// Applies the event and persists it, returns the budget after the change
+    fn record(&mut self, event: Event) -> Result<Budget, String>;
+
+    fn history(&self) -> Result<Vec<Event>, String> {
+        Err(String::from("History is only kept by the journal store"))
+    }
+
+    fn undo(&mut self) -> Result<Event, String> {
+        Err(String::from("Undo is only supported by the journal store"))
+    }
+
+    /// Drops undone events, returns the number of removed entries
+    fn compact(&mut self) -> Result<usize, String> {
+        Err(String::from(
+            "Compaction is only supported by the journal store",
+        ))
+    }
+}
+
+// Rejects events that would not read back the same way, e.g. a description containing the separator
+fn check_round_trip(event: &Event) -> Result<(), String> {
+    let line = event.to_string();
+    match Event::try_from(line.as_str()) {
+        Ok(parsed) if parsed.to_string() == line => Ok(()),
+        _ => Err(format!("Entry cannot be stored: {line}")),
+    }
+}
+
+fn replay(events: &[Event]) -> Result<Budget, String> {
+    let mut budget = Budget::default();
+    for event in events {
+        budget.apply(event)?;
+    }
+    Ok(budget)
+}
+
+fn read_lines(path: &PathBuf) -> Result<Vec<String>, String> {
+    match File::open(path) {
+        Ok(file) => BufReader::new(file)
+            .lines()
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string()),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(format!("Could not open {}: {error}", path.display())),
+    }
+}
+
+/// Keeps the current state only, every change rewrites the whole file
+pub struct FileStore {
+    path: PathBuf,
+}
+
+impl FileStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileStore { path: path.into() }
+    }
+
+    fn save(&self, budget: &Budget) -> Result<(), String> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&self.path)
+            .map_err(|e| format!("Could not open {}: {e}", self.path.display()))?;
+        let mut lines = vec![budget.rates.to_string()];
+        lines.extend(budget.recurring.iter().map(ToString::to_string));
+        lines.extend(budget.operations.iter().map(ToString::to_string));
+        lines
+            .iter()
+            .try_for_each(|line| writeln!(file, "{line}"))
+            .map_err(|e| format!("Could not write to file: {e}"))
+    }
+}
+
+impl BudgetStore for FileStore {
+    fn load(&self) -> Result<Budget, String> {
+        let mut budget = Budget::default();
+        for line in read_lines(&self.path)? {
+            if line.starts_with(BASE) || line.starts_with(RATE) {
+                budget.rates.parse_line(&line)?;
+            } else if line.starts_with(RECURRING) {
+                budget.recurring.push(line.as_str().try_into()?);
+            } else {
+                budget.operations.push(line.as_str().try_into()?);
+            }
+        }
+        Ok(budget)
+    }
+
+    fn record(&mut self, event: Event) -> Result<Budget, String> {
+        check_round_trip(&event)?;
+        let mut budget = self.load()?;
+        budget.apply(&event)?;
+        self.save(&budget)?;
+        Ok(budget)
+    }
+}
+
+/// Appends one line per event, the state is rebuilt by replaying the journal
+pub struct JournalStore {
+    path: PathBuf,
+}
+
+impl JournalStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        JournalStore { path: path.into() }
+    }
+
+    fn append(&self, line: &str) -> Result<(), String> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Could not open {}: {e}", self.path.display()))?;
+        writeln!(file, "{line}").map_err(|e| format!("Could not write to file: {e}"))
+    }
+
+    // UNDO entries cancel the most recent event that is still in effect
+    fn events(&self) -> Result<(Vec<Event>, usize), String> {
+        let lines = read_lines(&self.path)?;
+        let mut events = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            if line == UNDO {
+                events.pop();
+            } else {
+                let event = Event::try_from(line.as_str())
+                    .map_err(|message| format!("Line {}: {message}", index + 1))?;
+                events.push(event);
+            }
+        }
+        Ok((events, lines.len()))
+    }
+}
+
+impl BudgetStore for JournalStore {
+    fn load(&self) -> Result<Budget, String> {
+        replay(&self.events()?.0)
+    }
+
+    fn record(&mut self, event: Event) -> Result<Budget, String> {
+        check_round_trip(&event)?;
+        let mut budget = self.load()?;
+        budget.apply(&event)?;
+        self.append(&event.to_string())?;
+        Ok(budget)
+    }
+
+    fn history(&self) -> Result<Vec<Event>, String> {
+        Ok(self.events()?.0)
+    }
+
+    fn undo(&mut self) -> Result<Event, String> {
+        let (mut events, _) = self.events()?;
+        let event = events.pop().ok_or("Nothing to undo")?;
+        self.append(UNDO)?;
+        Ok(event)
+    }
+
+    fn compact(&mut self) -> Result<usize, String> {
+        let (events, lines_count) = self.events()?;
+        let temporary = self.path.with_extension("compacting");
+        let mut file = File::create(&temporary)
+            .map_err(|e| format!("Could not open {}: {e}", temporary.display()))?;
+        events
+            .iter()
+            .try_for_each(|event| writeln!(file, "{event}"))
+            .map_err(|e| format!("Could not write to file: {e}"))?;
+        std::fs::rename(&temporary, &self.path).map_err(|e| e.to_string())?;
+        Ok(lines_count - events.len())
+    }
+}
+
+/// Keeps events in memory only, useful in tests
+#[derive(Default)]
+pub struct MemoryStore {
+    events: Vec<Event>,
+}
+
+impl BudgetStore for MemoryStore {
+    fn load(&self) -> Result<Budget, String> {
+        replay(&self.events)
+    }
+
+    fn record(&mut self, event: Event) -> Result<Budget, String> {
+        check_round_trip(&event)?;
+        let mut budget = self.load()?;
+        budget.apply(&event)?;
+        self.events.push(event);
+        Ok(budget)
+    }
+
+    fn history(&self) -> Result<Vec<Event>, String> {
+        Ok(self.events.clone())
+    }
+
+    fn undo(&mut self) -> Result<Event, String> {
+        self.events
+            .pop()
+            .ok_or_else(|| String::from("Nothing to undo"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exercises::budget::OperationType;
+    use crate::exercises::budget::recurring::Schedule;
+    use crate::exercises::money_with_enums::MonetaryAmount;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn operation(amount: f64, description: &str, operation_type: OperationType) -> Operation {
+        Operation {
+            amount: MonetaryAmount::new(amount, &Currency::Pln),
+            description: description.to_string(),
+            operation_type,
+            date: date("2026-05-10"),
+            rule: None,
+        }
+    }
+
+    fn temporary_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("budget-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn memory_store_records_and_undoes_operations() {
+        let mut store = MemoryStore::default();
+        store
+            .record(Event::Add(operation(
+                100.0,
+                "Salary",
+                OperationType::Deposit,
+            )))
+            .unwrap();
+        let budget = store
+            .record(Event::Add(operation(
+                30.0,
+                "Lunch",
+                OperationType::Withdraw,
+            )))
+            .unwrap();
+        assert_eq!(budget.balance(), Ok(70.0));
+
+        store.undo().unwrap();
+
+        assert_eq!(store.load().unwrap().balance(), Ok(100.0));
+        assert_eq!(store.history().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rejected_event_is_not_recorded() {
+        let mut store = MemoryStore::default();
+
+        let result = store.record(Event::Rate(Currency::Eur, -1.0));
+
+        assert!(result.is_err());
+        assert!(store.history().unwrap().is_empty());
+    }
+
+    #[test]
+    fn sync_replays_to_the_same_state() {
+        let mut store = MemoryStore::default();
+        let rule = RecurringRule::new(
+            MonetaryAmount::new(1000.0, &Currency::Pln),
+            String::from("Rent"),
+            OperationType::Withdraw,
+            Schedule::Monthly(1),
+            date("2026-01-01"),
+        );
+        store.record(Event::Recur(rule)).unwrap();
+
+        let synced = store.record(Event::Sync(date("2026-03-15"))).unwrap();
+
+        assert_eq!(synced.operations.len(), 3);
+        assert_eq!(store.load().unwrap().balance(), Ok(-3000.0));
+        assert_eq!(store.load().unwrap().recurring[0].next, date("2026-04-01"));
+    }
+
+    #[test]
+    fn journal_undo_survives_reopening_and_compaction() {
+        let path = temporary_path("undo");
+        let mut store = JournalStore::new(&path);
+        store.record(Event::Base(Currency::Pln)).unwrap();
+        store.record(Event::Rate(Currency::Eur, 4.25)).unwrap();
+        assert!(
+            store
+                .record(Event::Add(operation(
+                    10.0,
+                    "Coffee; to go",
+                    OperationType::Withdraw,
+                )))
+                .is_err()
+        );
+        store
+            .record(Event::Add(operation(
+                10.0,
+                "Coffee",
+                OperationType::Withdraw,
+            )))
+            .unwrap();
+        store.undo().unwrap();
+
+        let mut reopened = JournalStore::new(&path);
+        assert_eq!(reopened.history().unwrap().len(), 2);
+        assert_eq!(reopened.compact().unwrap(), 2);
+        assert_eq!(
+            read_lines(&path).unwrap(),
+            vec!["BASE;PLN", "RATE;EUR;4.25"]
+        );
+        assert_eq!(reopened.load().unwrap().balance(), Ok(0.0));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn file_store_does_not_support_undo() {
+        let mut store = FileStore::new(temporary_path("file"));
+
+        assert!(store.undo().is_err());
+        assert!(store.history().is_err());
+        assert!(store.compact().is_err());
+    }
+}
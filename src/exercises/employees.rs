@@ -1,87 +1,410 @@
-use std::collections::HashMap;
+mod clock;
+mod holidays;
+mod payroll;
+mod report;
+mod stats;
+mod validation;
+
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
-use std::fs::File;
-use std::io::Write;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 
-use chrono::{Duration, NaiveDate, NaiveTime};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use clap::{Parser, Subcommand};
 use csv::ReaderBuilder;
 
+use clock::{Clock, SystemClock, clock_in, clock_out, status};
+use holidays::Holidays;
+use payroll::{Period, RATES_FILE_NAME, load_rates, payroll};
+use report::{DurationStyle, Language, OutputFormat, ReportOptions, render};
+use stats::{EmployeeStats, week_start};
+use validation::{Issue, Problem, parse_record, validate};
+
+const INPUT_FILE_NAME: &str = "work_log.csv";
+const OUTPUT_FILE_NAME: &str = "report";
+const OVERTIME_THRESHOLD: &str = "8";
+
 #[derive(Debug)]
 struct WorkEntry {
+    line: u64,
     employee_id: String,
     date: NaiveDate,
     start_time: NaiveTime,
     end_time: NaiveTime,
+    break_duration: Duration,
 }
 
-fn read_csv(path: &str) -> Result<Vec<WorkEntry>, Box<dyn Error>> {
-    let mut rdr = ReaderBuilder::new().from_path(path)?;
-    let mut entries = Vec::new();
+impl WorkEntry {
+    fn start(&self) -> NaiveDateTime {
+        self.date.and_time(self.start_time)
+    }
 
-    for result in rdr.records() {
-        let record = result?;
-        let entry = WorkEntry {
-            employee_id: record[0].to_string(),
-            date: NaiveDate::parse_from_str(&record[1], "%Y-%m-%d")?,
-            start_time: NaiveTime::parse_from_str(&record[2], "%H:%M")?,
-            end_time: NaiveTime::parse_from_str(&record[3], "%H:%M")?,
-        };
-        entries.push(entry);
+    /// Shifts ending at or before their start time end on the next day
+    fn end(&self) -> NaiveDateTime {
+        let end = self.date.and_time(self.end_time);
+        if self.end_time <= self.start_time {
+            end + Duration::days(1)
+        } else {
+            end
+        }
     }
 
-    Ok(entries)
+    fn worked(&self) -> Duration {
+        self.end() - self.start() - self.break_duration
+    }
 }
 
-fn analyze(entries: Vec<WorkEntry>) -> HashMap<String, (Duration, usize, Vec<String>)> {
-    let mut stats: HashMap<String, (Duration, usize, Vec<String>)> = HashMap::new();
+/// Limits the analysis to a date range and chosen employees, empty filter lets everything in
+#[derive(Debug, Default)]
+struct Filter {
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    employees: Vec<String>,
+}
 
-    for entry in entries {
-        let work_duration = entry.end_time - entry.start_time;
+impl Filter {
+    fn matches(&self, entry: &WorkEntry) -> bool {
+        self.days().contains(&entry.date)
+            && (self.employees.is_empty() || self.employees.contains(&entry.employee_id))
+    }
 
-        let emp_stat =
-            stats
-                .entry(entry.employee_id.clone())
-                .or_insert((Duration::zero(), 0, vec![]));
-        emp_stat.0 = emp_stat.0 + work_duration;
-        emp_stat.1 += 1;
+    fn days(&self) -> RangeInclusive<NaiveDate> {
+        self.from.unwrap_or(NaiveDate::MIN)..=self.to.unwrap_or(NaiveDate::MAX)
+    }
 
-        if work_duration > Duration::hours(8) {
-            emp_stat.2.push(entry.date.to_string());
+    /// Same filter with the range widened to the Monday before `from` and the Sunday after `to`
+    fn whole_weeks(&self) -> Filter {
+        Filter {
+            from: self.from.map(week_start),
+            to: self.to.map(|to| week_start(to) + Duration::days(6)),
+            employees: self.employees.clone(),
         }
     }
+}
+
+#[derive(Parser)]
+#[command(
+    name = "timesheet",
+    about = "Work time analysis",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// CSV work log with employee_id, date, start_time, end_time and optional break_minutes columns
+    #[arg(short, long, global = true, default_value = INPUT_FILE_NAME)]
+    input: PathBuf,
+    /// Report file, report with the extension of --format by default
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Daily hours after which a day counts as overtime, e.g. 8, 7.5 or 7:30
+    #[arg(long, global = true, default_value = OVERTIME_THRESHOLD, value_parser = parse_hours)]
+    overtime_threshold: Duration,
+    /// First day to include
+    #[arg(long, global = true)]
+    from: Option<NaiveDate>,
+    /// Last day to include
+    #[arg(long, global = true)]
+    to: Option<NaiveDate>,
+    /// Only this employee, can be repeated
+    #[arg(short, long, global = true)]
+    employee: Vec<String>,
+    /// Holiday calendar with one YYYY-MM-DD[,name] per line
+    #[arg(long, global = true)]
+    holidays: Option<PathBuf>,
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// Language of report labels and messages
+    #[arg(short, long, value_enum, global = true, default_value_t = Language::Pl)]
+    locale: Language,
+    /// Durations as 7h 30min or as decimal hours 7.50
+    #[arg(short, long, value_enum, global = true, default_value_t = DurationStyle::Clock)]
+    durations: DurationStyle,
+}
 
-    stats
+#[derive(Subcommand)]
+enum Command {
+    /// Start a shift now, e.g. clock-in E001
+    ClockIn {
+        #[arg(value_name = "EMPLOYEE")]
+        employee_id: String,
+    },
+    /// End the employee's open shift now
+    ClockOut {
+        #[arg(value_name = "EMPLOYEE")]
+        employee_id: String,
+    },
+    /// Show who is clocked in
+    Status,
+    /// Pay for worked hours per period, with overtime paid at the employee's multiplier
+    Payroll {
+        /// CSV with employee_id, hourly_rate, overtime_multiplier and currency columns
+        #[arg(short, long, default_value = RATES_FILE_NAME)]
+        rates: PathBuf,
+        #[arg(short, long, value_enum, default_value_t = Period::Month)]
+        period: Period,
+        /// Also write the payroll as CSV to this file
+        #[arg(long)]
+        csv: Option<PathBuf>,
+        /// Pay all hours worked on a holiday from --holidays as overtime
+        #[arg(long)]
+        holiday_overtime: bool,
+    },
+}
+
+fn parse_hours(value: &str) -> Result<Duration, String> {
+    let minutes = match value.split_once(':') {
+        Some((hours, minutes)) => match (hours.parse::<i64>(), minutes.parse::<i64>()) {
+            (Ok(hours), Ok(minutes)) if (0..60).contains(&minutes) => Some(hours * 60 + minutes),
+            _ => None,
+        },
+        None => value
+            .parse::<f64>()
+            .ok()
+            .filter(|hours| hours.is_finite())
+            .map(|hours| (hours * 60.0).round() as i64),
+    };
+    match minutes {
+        Some(minutes) if (0..=24 * 60).contains(&minutes) => Ok(Duration::minutes(minutes)),
+        _ => Err(format!("Invalid number of hours: {value}")),
+    }
+}
+
+/// Collects every bad row as an issue instead of stopping at the first one
+fn read_csv(path: &Path) -> Result<(Vec<WorkEntry>, Vec<Issue>), Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new().flexible(true).from_path(path)?;
+    let mut entries = Vec::new();
+    let mut issues = Vec::new();
+
+    for result in rdr.records() {
+        let parsed = match result {
+            // Shifts still open after clock-in are counted once clocked out
+            Ok(record) if record.get(3).is_some_and(|end| end.trim().is_empty()) => continue,
+            Ok(record) => {
+                let line = record.position().map_or(0, |position| position.line());
+                parse_record(&record, line).map_err(|problem| Issue { line, problem })
+            }
+            Err(error) => Err(Issue {
+                line: error.position().map_or(0, |position| position.line()),
+                problem: Problem::Unreadable(error.to_string()),
+            }),
+        };
+        match parsed {
+            Ok(entry) => entries.push(entry),
+            Err(issue) => issues.push(issue),
+        }
+    }
+
+    let (entries, mut conflicts) = validate(entries);
+    issues.append(&mut conflicts);
+    issues.sort_by_key(|issue| issue.line);
+    Ok((entries, issues))
+}
+
+fn analyze(
+    entries: &[WorkEntry],
+    filter: &Filter,
+    overtime_threshold: Duration,
+    holidays: &Holidays,
+) -> BTreeMap<String, EmployeeStats> {
+    let mut shifts: HashMap<&str, Vec<&WorkEntry>> = HashMap::new();
+    for entry in entries.iter().filter(|entry| filter.matches(entry)) {
+        shifts.entry(&entry.employee_id).or_default().push(entry);
+    }
+
+    shifts
+        .into_iter()
+        .map(|(employee, shifts)| {
+            let stats = EmployeeStats::from_shifts(&shifts, overtime_threshold, holidays);
+            (employee.to_string(), stats)
+        })
+        .collect()
 }
 
 fn write_report(
-    stats: &HashMap<String, (Duration, usize, Vec<String>)>,
-    path: &str,
+    stats: &BTreeMap<String, EmployeeStats>,
+    issues: &[Issue],
+    options: ReportOptions,
+    path: &Path,
 ) -> Result<(), Box<dyn Error>> {
-    let mut file = File::create(path)?;
+    let report = render(stats, issues, options)?;
+    fs::write(path, report)?;
+    Ok(())
+}
 
-    for (employee, (total_duration, day_count, overtime_days)) in stats {
-        let avg_minutes = total_duration.num_minutes() as f64 / *day_count as f64;
-        let hours = total_duration.num_minutes() / 60;
-        let minutes = total_duration.num_minutes() % 60;
+fn timesheet(cli: Cli, clock: &impl Clock) -> Result<(), Box<dyn Error>> {
+    let labels = cli.locale.labels();
+    let minutes = |time: NaiveDateTime| time.format("%Y-%m-%d %H:%M");
+    match cli.command {
+        Some(Command::ClockIn {
+            employee_id: employee,
+        }) => {
+            let start =
+                clock_in(&cli.input, &employee, clock).map_err(|error| error.message(labels))?;
+            println!("{employee}: {} {}", labels.clocked_in, minutes(start));
+        }
+        Some(Command::ClockOut {
+            employee_id: employee,
+        }) => {
+            let (start, end) =
+                clock_out(&cli.input, &employee, clock).map_err(|error| error.message(labels))?;
+            println!(
+                "{employee}: {} {} ({})",
+                labels.clocked_out,
+                minutes(end),
+                cli.durations.format(end - start)
+            );
+        }
+        Some(Command::Status) => {
+            let open = status(&cli.input).map_err(|error| error.message(labels))?;
+            if open.is_empty() {
+                println!("{}", labels.nobody_clocked_in);
+            }
+            for shift in open {
+                println!(
+                    "{}: {} {} ({})",
+                    shift.employee_id,
+                    labels.working_since,
+                    minutes(shift.start),
+                    cli.durations.format(clock.now() - shift.start)
+                );
+            }
+        }
+        Some(Command::Payroll {
+            ref rates,
+            period,
+            ref csv,
+            holiday_overtime,
+        }) => {
+            let rates = load_rates(rates)?;
+            let filter = filter(&cli)?;
+            let (entries, issues) = read_csv(&cli.input)?;
+            // Weekly overtime needs the whole first and last week, only the filtered days are paid
+            let stats = analyze(
+                &entries,
+                &filter.whole_weeks(),
+                cli.overtime_threshold,
+                &holidays(&cli)?,
+            );
+            let payroll = payroll(
+                &stats,
+                &rates,
+                cli.overtime_threshold,
+                holiday_overtime,
+                period,
+                filter.days(),
+            )?;
+            print!("{}", payroll::to_text(&payroll, cli.locale, cli.durations)?);
+            if let Some(path) = csv {
+                fs::write(path, payroll::to_csv(&payroll)?)?;
+                println!("{} {}", labels.saved, path.display());
+            }
+            if !issues.is_empty() {
+                println!("{}: {}", labels.skipped_rows, issues.len());
+            }
+        }
+        None => report(cli)?,
+    }
+    Ok(())
+}
 
-        writeln!(file, "Pracownik: {}", employee)?;
-        writeln!(file, "  Całkowity czas pracy: {}h {}min", hours, minutes)?;
-        writeln!(
-            file,
-            "  Średnia długość dnia pracy: {:.2}h",
-            avg_minutes / 60.0
-        )?;
-        writeln!(file, "  Dni z nadgodzinami: {:?}", overtime_days)?;
-        writeln!(file)?;
+fn filter(cli: &Cli) -> Result<Filter, String> {
+    if let (Some(from), Some(to)) = (cli.from, cli.to)
+        && from > to
+    {
+        return Err(format!("--from {from} is after --to {to}"));
     }
+    Ok(Filter {
+        from: cli.from,
+        to: cli.to,
+        employees: cli.employee.clone(),
+    })
+}
 
-    Ok(())
+fn holidays(cli: &Cli) -> Result<Holidays, String> {
+    match &cli.holidays {
+        Some(path) => Holidays::load(path),
+        None => Ok(Holidays::default()),
+    }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let entries = read_csv("work_log.csv")?;
-    let stats = analyze(entries);
-    write_report(&stats, "report.txt")?;
-    println!("Raport zapisany do report.txt");
+fn report(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let filter = filter(&cli)?;
+
+    let holidays = holidays(&cli)?;
+    let (entries, issues) = read_csv(&cli.input)?;
+    let stats = analyze(&entries, &filter, cli.overtime_threshold, &holidays);
+    let options = ReportOptions {
+        format: cli.format,
+        language: cli.locale,
+        durations: cli.durations,
+    };
+    let output = cli
+        .output
+        .unwrap_or_else(|| Path::new(OUTPUT_FILE_NAME).with_extension(cli.format.extension()));
+    write_report(&stats, &issues, options, &output)?;
+    let labels = cli.locale.labels();
+    println!("{} {}", labels.saved, output.display());
+    if !issues.is_empty() {
+        println!("{}: {}", labels.skipped_rows, issues.len());
+    }
     Ok(())
 }
+
+pub fn run() {
+    if let Err(error) = timesheet(Cli::parse(), &SystemClock) {
+        println!("Error: {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(employee_id: &str, date: &str, start_time: &str, end_time: &str) -> WorkEntry {
+        WorkEntry {
+            line: 0,
+            employee_id: employee_id.to_string(),
+            date: date.parse().unwrap(),
+            start_time: start_time.parse().unwrap(),
+            end_time: end_time.parse().unwrap(),
+            break_duration: Duration::zero(),
+        }
+    }
+
+    #[test]
+    fn parses_overtime_threshold() {
+        assert_eq!(parse_hours("8"), Ok(Duration::hours(8)));
+        assert_eq!(parse_hours("7.5"), Ok(Duration::minutes(450)));
+        assert_eq!(parse_hours("7:30"), Ok(Duration::minutes(450)));
+        assert!(parse_hours("7:75").is_err());
+        assert!(parse_hours("-1").is_err());
+        assert!(parse_hours("eight").is_err());
+    }
+
+    #[test]
+    fn analyze_applies_filter_and_threshold() {
+        let entries = vec![
+            entry("E001", "2026-03-02", "08:00", "16:30"),
+            entry("E001", "2026-03-03", "08:00", "15:00"),
+            entry("E001", "2026-03-10", "08:00", "18:00"),
+            entry("E002", "2026-03-02", "09:00", "17:00"),
+        ];
+        let filter = Filter {
+            from: Some("2026-03-01".parse().unwrap()),
+            to: Some("2026-03-07".parse().unwrap()),
+            employees: vec![String::from("E001")],
+        };
+
+        let stats = analyze(&entries, &filter, Duration::hours(7), &Holidays::default());
+
+        assert_eq!(stats.len(), 1);
+        let stats = &stats["E001"];
+        assert_eq!(stats.total, Duration::minutes(15 * 60 + 30));
+        assert_eq!(stats.days(), 2);
+        assert_eq!(stats.overtime_days, vec!["2026-03-02".parse().unwrap()]);
+    }
+}
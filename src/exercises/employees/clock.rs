@@ -0,0 +1,378 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::ops::Range;
+use std::path::Path;
+
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use csv::{ReaderBuilder, StringRecord};
+
+use crate::exercises::utils::csv_string;
+
+use super::report::Labels;
+
+const HEADER: [&str; 5] = [
+    "employee_id",
+    "date",
+    "start_time",
+    "end_time",
+    "break_minutes",
+];
+const TIME_FORMAT: &str = "%H:%M";
+const MINUTE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+pub trait Clock {
+    fn now(&self) -> NaiveDateTime;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
+
+/// Row with a start time and an empty end time, written by `clock_in`
+#[derive(Debug, PartialEq)]
+pub struct OpenShift {
+    pub line: u64,
+    pub employee_id: String,
+    pub start: NaiveDateTime,
+    break_minutes: String,
+    /// Bytes of the whole record in the log, quoted fields may span lines
+    bytes: Range<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ClockError {
+    Read(String),
+    Write(String),
+    AlreadyClockedIn {
+        employee: String,
+        since: NaiveDateTime,
+    },
+    NotClockedIn(String),
+    TooShort {
+        employee: String,
+        since: NaiveDateTime,
+    },
+    /// Open for a day or more, the log line has to be fixed by hand
+    Stale {
+        employee: String,
+        since: NaiveDateTime,
+        line: u64,
+    },
+}
+
+impl ClockError {
+    pub fn message(&self, labels: &Labels) -> String {
+        match self {
+            ClockError::Read(error) => format!("{} {error}", labels.cannot_read),
+            ClockError::Write(error) => format!("{} {error}", labels.cannot_write),
+            ClockError::AlreadyClockedIn { employee, since } => format!(
+                "{employee}: {} {}",
+                labels.already_clocked_in,
+                since.format(MINUTE_FORMAT)
+            ),
+            ClockError::NotClockedIn(employee) => {
+                format!("{employee}: {}", labels.not_clocked_in)
+            }
+            ClockError::TooShort { employee, since } => format!(
+                "{employee}: {} {}",
+                labels.shift_too_short,
+                since.format(MINUTE_FORMAT)
+            ),
+            ClockError::Stale {
+                employee,
+                since,
+                line,
+            } => format!(
+                "{employee}: {} {}, {} {line}",
+                labels.stale_shift,
+                since.format(MINUTE_FORMAT),
+                labels.fix_line
+            ),
+        }
+    }
+}
+
+/// The log stores minutes only, so clock times are cut to the full minute
+fn current_minute(clock: &impl Clock) -> NaiveDateTime {
+    let now = clock.now();
+    now.with_second(0)
+        .and_then(|now| now.with_nanosecond(0))
+        .unwrap_or(now)
+}
+
+fn read_log(path: &Path) -> Result<String, ClockError> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(String::new()),
+        Err(error) => Err(ClockError::Read(format!("{}: {error}", path.display()))),
+    }
+}
+
+/// Writes a temporary file next to the log and renames it, so the log is never left half written
+fn write_log(path: &Path, content: &str) -> Result<(), ClockError> {
+    let write_error =
+        |error: std::io::Error| ClockError::Write(format!("{}: {error}", path.display()));
+    let temporary = path.with_extension("writing");
+    fs::write(&temporary, content).map_err(write_error)?;
+    fs::rename(&temporary, path).map_err(write_error)
+}
+
+fn to_line(fields: &[&str]) -> Result<String, ClockError> {
+    csv_string(&[fields.iter().map(ToString::to_string).collect()]).map_err(ClockError::Write)
+}
+
+fn open_shift(record: &StringRecord, bytes: Range<usize>) -> Option<OpenShift> {
+    if !record.get(3)?.trim().is_empty() {
+        return None;
+    }
+    let date = NaiveDate::parse_from_str(record.get(1)?.trim(), "%Y-%m-%d").ok()?;
+    let start = NaiveTime::parse_from_str(record.get(2)?.trim(), TIME_FORMAT).ok()?;
+    Some(OpenShift {
+        line: record.position()?.line(),
+        employee_id: record.get(0)?.trim().to_string(),
+        start: date.and_time(start),
+        break_minutes: record.get(4).unwrap_or_default().trim().to_string(),
+        bytes,
+    })
+}
+
+/// Open shifts in the log, rows that cannot be read are left for the report validation
+fn open_shifts(content: &str) -> Vec<OpenShift> {
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let mut shifts = Vec::new();
+    let mut record = StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(_) => continue,
+        }
+        // The reader stands after the record's line terminator now
+        let end = reader.position().byte() as usize;
+        if let Some(position) = record.position() {
+            shifts.extend(open_shift(&record, position.byte() as usize..end));
+        }
+    }
+    shifts
+}
+
+pub fn status(path: &Path) -> Result<Vec<OpenShift>, ClockError> {
+    Ok(open_shifts(&read_log(path)?))
+}
+
+/// Appends an open shift starting now, the log is created with a header when missing
+pub fn clock_in(
+    path: &Path,
+    employee_id: &str,
+    clock: &impl Clock,
+) -> Result<NaiveDateTime, ClockError> {
+    let content = read_log(path)?;
+    if let Some(open) = open_shifts(&content)
+        .into_iter()
+        .find(|open| open.employee_id == employee_id)
+    {
+        return Err(ClockError::AlreadyClockedIn {
+            employee: employee_id.to_string(),
+            since: open.start,
+        });
+    }
+
+    let start = current_minute(clock);
+    let mut content = content;
+    if content.trim().is_empty() {
+        content = to_line(&HEADER)?;
+    } else if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&to_line(&[
+        employee_id,
+        &start.date().to_string(),
+        &start.format(TIME_FORMAT).to_string(),
+        "",
+        "",
+    ])?);
+    write_log(path, &content)?;
+    Ok(start)
+}
+
+/// Fills in the end time of the employee's open shift, returns the closed shift's start and end
+pub fn clock_out(
+    path: &Path,
+    employee_id: &str,
+    clock: &impl Clock,
+) -> Result<(NaiveDateTime, NaiveDateTime), ClockError> {
+    let content = read_log(path)?;
+    let open = open_shifts(&content)
+        .into_iter()
+        .find(|open| open.employee_id == employee_id)
+        .ok_or_else(|| ClockError::NotClockedIn(employee_id.to_string()))?;
+
+    let end = current_minute(clock);
+    if end <= open.start {
+        return Err(ClockError::TooShort {
+            employee: employee_id.to_string(),
+            since: open.start,
+        });
+    }
+    if end - open.start >= Duration::days(1) {
+        return Err(ClockError::Stale {
+            employee: employee_id.to_string(),
+            since: open.start,
+            line: open.line,
+        });
+    }
+
+    let closed = to_line(&[
+        employee_id,
+        &open.start.date().to_string(),
+        &open.start.format(TIME_FORMAT).to_string(),
+        &end.format(TIME_FORMAT).to_string(),
+        &open.break_minutes,
+    ])?;
+    let mut content = content;
+    content.replace_range(open.bytes, &closed);
+    write_log(path, &content)?;
+    Ok((open.start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::exercises::employees::report::Language;
+
+    struct FixedClock(Cell<NaiveDateTime>);
+
+    impl FixedClock {
+        fn at(value: &str) -> Self {
+            FixedClock(Cell::new(value.parse().unwrap()))
+        }
+
+        fn set(&self, value: &str) {
+            self.0.set(value.parse().unwrap());
+        }
+    }
+
+    impl Clock for FixedClock {
+        fn now(&self) -> NaiveDateTime {
+            self.0.get()
+        }
+    }
+
+    fn temporary_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("timesheet-{name}-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn clock_in_and_out_write_one_row_per_shift() {
+        let path = temporary_path("shift");
+        let clock = FixedClock::at("2026-03-02T07:58:41");
+
+        assert!(clock_in(&path, "E001", &clock).is_ok());
+        clock.set("2026-03-02T08:03:00");
+        assert!(clock_in(&path, "E002", &clock).is_ok());
+        assert!(clock_in(&path, "E001", &clock).is_err());
+        assert_eq!(
+            status(&path)
+                .unwrap()
+                .iter()
+                .map(|open| open.employee_id.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["E001", "E002"]
+        );
+
+        clock.set("2026-03-02T16:00:30");
+        let (start, end) = clock_out(&path, "E001", &clock).unwrap();
+
+        assert_eq!(start, "2026-03-02T07:58:00".parse().unwrap());
+        assert_eq!(end, "2026-03-02T16:00:00".parse().unwrap());
+        assert!(clock_out(&path, "E001", &clock).is_err());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "employee_id,date,start_time,end_time,break_minutes\n\
+             E001,2026-03-02,07:58,16:00,\n\
+             E002,2026-03-02,08:03,,\n"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn clock_out_keeps_records_spanning_lines() {
+        let path = temporary_path("quoted");
+        let log = "employee_id,date,start_time,end_time,break_minutes\n\
+                   \"E003\nnight\",2026-03-01,22:00,06:00,\n\
+                   E001,2026-03-02,08:00,,15\n\
+                   E002,2026-03-02,09:00,,\n";
+        fs::write(&path, log).unwrap();
+        let clock = FixedClock::at("2026-03-02T16:00:00");
+
+        clock_out(&path, "E001", &clock).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            log.replace("08:00,,15", "08:00,16:00,15")
+        );
+        assert!(!path.with_extension("writing").exists());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn errors_are_described_in_the_report_language() {
+        let path = temporary_path("errors");
+        let clock = FixedClock::at("2026-03-02T07:58:00");
+        clock_in(&path, "E001", &clock).unwrap();
+
+        let twice = clock_in(&path, "E001", &clock).unwrap_err();
+        let absent = clock_out(&path, "E002", &clock).unwrap_err();
+
+        assert_eq!(
+            twice.message(Language::Pl.labels()),
+            "E001: już w pracy od 2026-03-02 07:58"
+        );
+        assert_eq!(
+            twice.message(Language::En.labels()),
+            "E001: already clocked in since 2026-03-02 07:58"
+        );
+        assert_eq!(
+            absent.message(Language::Pl.labels()),
+            "E002: nie jest w pracy"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn clock_out_closes_overnight_shift_and_rejects_stale_ones() {
+        let path = temporary_path("overnight");
+        fs::write(
+            &path,
+            "employee_id,date,start_time,end_time\nE001,2026-03-01,08:00,\nE002,2026-03-01,22:00,",
+        )
+        .unwrap();
+        let clock = FixedClock::at("2026-03-02T08:30:00");
+
+        assert!(clock_out(&path, "E002", &clock).is_ok());
+        assert_eq!(
+            clock_out(&path, "E001", &clock),
+            Err(ClockError::Stale {
+                employee: String::from("E001"),
+                since: "2026-03-01T08:00:00".parse().unwrap(),
+                line: 2
+            })
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "employee_id,date,start_time,end_time\nE001,2026-03-01,08:00,\nE002,2026-03-01,22:00,08:30,\n"
+        );
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::NaiveDate;
+
+const SEPARATOR: char = ',';
+const COMMENT: char = '#';
+
+/// Public holidays read from lines of `YYYY-MM-DD[,name]`, `#` starts a comment
+#[derive(Debug, Default)]
+pub struct Holidays {
+    days: BTreeMap<NaiveDate, String>,
+}
+
+impl Holidays {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|error| format!("Cannot read {}: {error}", path.display()))?;
+        Holidays::parse(&content).map_err(|error| format!("{}: {error}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut days = BTreeMap::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.split(COMMENT).next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (date, name) = line.split_once(SEPARATOR).unwrap_or((line, ""));
+            let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|_| format!("line {}: invalid date: {date}", index + 1))?;
+            days.insert(date, name.trim().to_string());
+        }
+        Ok(Holidays { days })
+    }
+
+    /// Name of the holiday on the date, empty when the calendar gives none
+    pub fn get(&self, date: NaiveDate) -> Option<&str> {
+        self.days.get(&date).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_calendar_with_comments_and_optional_names() {
+        let holidays = Holidays::parse(
+            "# Polish public holidays\n2026-01-01,Nowy Rok\n\n2026-05-03 # Konstytucja\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            holidays.get("2026-01-01".parse().unwrap()),
+            Some("Nowy Rok")
+        );
+        assert_eq!(holidays.get("2026-05-03".parse().unwrap()), Some(""));
+        assert_eq!(holidays.get("2026-05-04".parse().unwrap()), None);
+        assert_eq!(
+            Holidays::parse("2026-01-01\n2026-13-01,Bad").unwrap_err(),
+            "line 2: invalid date: 2026-13-01"
+        );
+    }
+}
@@ -0,0 +1,507 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use chrono::{Duration, NaiveDate};
+use csv::ReaderBuilder;
+
+use crate::exercises::money::{
+    Currency, Money, MoneyBag, MoneyError, MoneyFormatter, Rate, RoundingMode,
+};
+use crate::exercises::utils::csv_string;
+
+use super::report::{DurationStyle, Language};
+use super::stats::{EmployeeStats, WEEKLY_LIMIT_HOURS, month_start, week_start};
+
+pub const RATES_FILE_NAME: &str = "pay_rates.csv";
+const FIELDS_COUNT: usize = 4;
+const MINUTES_PER_HOUR: i64 = 60;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Period {
+    Week,
+    #[default]
+    Month,
+}
+
+impl Period {
+    pub fn start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Week => week_start(date),
+            Period::Month => month_start(date),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PayRate {
+    pub hourly: Money,
+    pub overtime_multiplier: Rate,
+}
+
+/// Reads `employee_id,hourly_rate,overtime_multiplier,currency` rows after a header,
+/// rates finer than the minor unit of their currency are rejected instead of rounded
+pub fn parse_rates(content: &str) -> Result<BTreeMap<String, PayRate>, String> {
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let mut rates = BTreeMap::new();
+    for result in reader.records() {
+        let record = result.map_err(|error| error.to_string())?;
+        let line = record.position().map_or(0, |position| position.line());
+        let invalid = |message: String| format!("line {line}: {message}");
+        if record.len() != FIELDS_COUNT {
+            return Err(invalid(format!(
+                "expected {FIELDS_COUNT} fields, found {}",
+                record.len()
+            )));
+        }
+        let currency =
+            Currency::from_code(record[3].trim()).map_err(|error| invalid(error.to_string()))?;
+        let fraction = record[1]
+            .trim()
+            .split_once('.')
+            .map_or("", |(_, fraction)| fraction);
+        if fraction.trim_end_matches('0').len() > currency.minor_units() as usize {
+            return Err(invalid(format!(
+                "hourly rate {} has more than {} decimals",
+                record[1].trim(),
+                currency.minor_units()
+            )));
+        }
+        // The fraction fits the minor unit, so no rounding happens
+        let hourly = Money::from_decimal(&record[1], currency, RoundingMode::Floor)
+            .map_err(|error| invalid(error.to_string()))?;
+        let overtime_multiplier =
+            Rate::parse(&record[2]).map_err(|error| invalid(error.to_string()))?;
+        if hourly.is_negative() {
+            return Err(invalid(format!("negative hourly rate: {}", &record[1])));
+        }
+        if overtime_multiplier.numerator() < overtime_multiplier.denominator() {
+            return Err(invalid(format!(
+                "overtime multiplier below 1: {}",
+                &record[2]
+            )));
+        }
+        let employee = record[0].trim().to_string();
+        if rates.contains_key(&employee) {
+            return Err(invalid(format!("second rate for {employee}")));
+        }
+        rates.insert(
+            employee,
+            PayRate {
+                hourly,
+                overtime_multiplier,
+            },
+        );
+    }
+    Ok(rates)
+}
+
+pub fn load_rates(path: &Path) -> Result<BTreeMap<String, PayRate>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|error| format!("Cannot read {}: {error}", path.display()))?;
+    parse_rates(&content).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+/// Splits each day into regular and overtime hours. Hours above the daily threshold are
+/// overtime, and so are regular hours that push the week above `WEEKLY_LIMIT_HOURS`.
+/// With `holiday_overtime` all hours worked on a holiday are overtime as well.
+pub fn split_hours(
+    stats: &EmployeeStats,
+    overtime_threshold: Duration,
+    holiday_overtime: bool,
+) -> BTreeMap<NaiveDate, (Duration, Duration)> {
+    let mut split = BTreeMap::new();
+    let mut week = None;
+    let mut regular_in_week = Duration::zero();
+    for (day, worked) in &stats.daily {
+        if week != Some(week_start(*day)) {
+            week = Some(week_start(*day));
+            regular_in_week = Duration::zero();
+        }
+        let holiday = stats.holiday_days.iter().any(|(date, _)| date == day);
+        let mut regular = match holiday_overtime && holiday {
+            true => Duration::zero(),
+            false => (*worked).min(overtime_threshold),
+        };
+        let above_week = regular_in_week + regular - Duration::hours(WEEKLY_LIMIT_HOURS);
+        if above_week > Duration::zero() {
+            regular -= above_week;
+        }
+        regular_in_week += regular;
+        split.insert(*day, (regular, *worked - regular));
+    }
+    split
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PayLine {
+    pub period: NaiveDate,
+    pub employee: String,
+    pub regular: Duration,
+    pub overtime: Duration,
+    pub regular_pay: Money,
+    pub overtime_pay: Money,
+    /// Part of `overtime_pay` above the base hourly rate
+    pub premium: Money,
+}
+
+impl PayLine {
+    pub fn total(&self) -> Result<Money, MoneyError> {
+        self.regular_pay.checked_add(self.overtime_pay)
+    }
+}
+
+fn base_pay(hourly: Money, worked: Duration) -> Result<Money, MoneyError> {
+    hourly.mul_ratio(
+        worked.num_minutes(),
+        MINUTES_PER_HOUR,
+        RoundingMode::HalfEven,
+    )
+}
+
+fn overtime_pay(hourly: Money, worked: Duration, multiplier: Rate) -> Result<Money, MoneyError> {
+    let numerator = i64::try_from(multiplier.numerator() * worked.num_minutes() as i128)
+        .map_err(|_| MoneyError::Overflow)?;
+    let denominator = i64::try_from(multiplier.denominator() * MINUTES_PER_HOUR as i128)
+        .map_err(|_| MoneyError::Overflow)?;
+    hourly.mul_ratio(numerator, denominator, RoundingMode::HalfEven)
+}
+
+#[derive(Debug, Default)]
+pub struct Payroll {
+    pub lines: Vec<PayLine>,
+    /// Employees with worked hours but no rate, they are left out of the lines
+    pub missing_rates: Vec<String>,
+}
+
+impl Payroll {
+    /// Totals of all lines in each period, one amount per currency
+    pub fn totals(&self) -> BTreeMap<NaiveDate, MoneyBag> {
+        let mut totals: BTreeMap<NaiveDate, MoneyBag> = BTreeMap::new();
+        for line in &self.lines {
+            let bag = totals.entry(line.period).or_default();
+            bag.add(line.regular_pay);
+            bag.add(line.overtime_pay);
+        }
+        totals
+    }
+}
+
+/// Pays every employee per period for the days in `paid_days`, each amount is rounded once to
+/// the minor unit. The stats should cover whole weeks, so weekly overtime is split correctly
+/// when `paid_days` starts or ends mid-week. With `holiday_overtime` hours worked on a holiday
+/// are paid as overtime.
+pub fn payroll(
+    stats: &BTreeMap<String, EmployeeStats>,
+    rates: &BTreeMap<String, PayRate>,
+    overtime_threshold: Duration,
+    holiday_overtime: bool,
+    period: Period,
+    paid_days: RangeInclusive<NaiveDate>,
+) -> Result<Payroll, MoneyError> {
+    let mut payroll = Payroll::default();
+    for (employee, stats) in stats {
+        if stats.daily.range(paid_days.clone()).next().is_none() {
+            continue;
+        }
+        let Some(rate) = rates.get(employee) else {
+            payroll.missing_rates.push(employee.clone());
+            continue;
+        };
+        let mut hours: BTreeMap<NaiveDate, (Duration, Duration)> = BTreeMap::new();
+        let split = split_hours(stats, overtime_threshold, holiday_overtime);
+        for (day, (regular, overtime)) in split.range(paid_days.clone()) {
+            let hours = hours
+                .entry(period.start(*day))
+                .or_insert((Duration::zero(), Duration::zero()));
+            hours.0 += *regular;
+            hours.1 += *overtime;
+        }
+        for (start, (regular, overtime)) in hours {
+            let overtime_pay = overtime_pay(rate.hourly, overtime, rate.overtime_multiplier)?;
+            payroll.lines.push(PayLine {
+                period: start,
+                employee: employee.clone(),
+                regular,
+                overtime,
+                regular_pay: base_pay(rate.hourly, regular)?,
+                overtime_pay,
+                premium: overtime_pay.checked_sub(base_pay(rate.hourly, overtime)?)?,
+            });
+        }
+    }
+    payroll
+        .lines
+        .sort_by(|a, b| (a.period, &a.employee).cmp(&(b.period, &b.employee)));
+    Ok(payroll)
+}
+
+pub fn to_text(
+    payroll: &Payroll,
+    language: Language,
+    durations: DurationStyle,
+) -> Result<String, MoneyError> {
+    let labels = language.labels();
+    let formatter = MoneyFormatter::new(language.money_locale());
+    let totals = payroll.totals();
+    let mut output = String::new();
+    for (period, total) in &totals {
+        let _ = writeln!(output, "{} {period}:", labels.period_from);
+        for line in payroll.lines.iter().filter(|line| line.period == *period) {
+            let _ = writeln!(
+                output,
+                "  {}: {} + {} {} -> {} + {} ({} {}) = {}",
+                line.employee,
+                durations.format(line.regular),
+                durations.format(line.overtime),
+                labels.overtime,
+                formatter.format(line.regular_pay),
+                formatter.format(line.overtime_pay),
+                labels.premium,
+                formatter.format(line.premium),
+                formatter.format(line.total()?)
+            );
+        }
+        let amounts: Vec<String> = total
+            .amounts()?
+            .into_iter()
+            .map(|amount| formatter.format(amount))
+            .collect();
+        let _ = writeln!(output, "  {}: {}", labels.total_pay, amounts.join(" + "));
+    }
+    if !payroll.missing_rates.is_empty() {
+        let _ = writeln!(
+            output,
+            "{}: {}",
+            labels.missing_rate,
+            payroll.missing_rates.join(", ")
+        );
+    }
+    Ok(output)
+}
+
+/// Plain decimal amounts, so the file can be reconciled in a spreadsheet
+pub fn to_csv(payroll: &Payroll) -> Result<String, String> {
+    let mut records = Vec::new();
+    records.push(vec![
+        "period".into(),
+        "employee".into(),
+        "regular_hours".into(),
+        "overtime_hours".into(),
+        "regular_pay".into(),
+        "overtime_pay".into(),
+        "premium".into(),
+        "total".into(),
+        "currency".into(),
+    ]);
+    for line in &payroll.lines {
+        records.push(vec![
+            line.period.to_string(),
+            line.employee.clone(),
+            DurationStyle::Decimal.value(line.regular),
+            DurationStyle::Decimal.value(line.overtime),
+            line.regular_pay.to_decimal_string(),
+            line.overtime_pay.to_decimal_string(),
+            line.premium.to_decimal_string(),
+            line.total().map_err(|e| e.to_string())?.to_decimal_string(),
+            line.regular_pay.currency().to_string(),
+        ]);
+    }
+    csv_string(&records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exercises::employees::WorkEntry;
+    use crate::exercises::employees::holidays::Holidays;
+
+    const RATES: &str = "employee_id,hourly_rate,overtime_multiplier,currency\n\
+                         E001,40.00,1.5,PLN\n\
+                         E002,12.340,2,EUR\n";
+
+    fn stats(shifts: &[(&str, &str, &str)]) -> EmployeeStats {
+        stats_with_holidays(shifts, &Holidays::default())
+    }
+
+    fn stats_with_holidays(shifts: &[(&str, &str, &str)], holidays: &Holidays) -> EmployeeStats {
+        let entries: Vec<WorkEntry> = shifts
+            .iter()
+            .map(|(date, start, end)| WorkEntry {
+                line: 0,
+                employee_id: String::from("E001"),
+                date: date.parse().unwrap(),
+                start_time: start.parse().unwrap(),
+                end_time: end.parse().unwrap(),
+                break_duration: Duration::zero(),
+            })
+            .collect();
+        let shifts: Vec<&WorkEntry> = entries.iter().collect();
+        EmployeeStats::from_shifts(&shifts, Duration::hours(8), holidays)
+    }
+
+    #[test]
+    fn parses_rates_and_rejects_bad_rows() {
+        let rates = parse_rates(RATES).unwrap();
+
+        assert_eq!(
+            rates["E001"].hourly,
+            Money::from_minor(4_000, Currency::PLN)
+        );
+        assert_eq!(
+            rates["E002"].hourly,
+            Money::from_minor(1_234, Currency::EUR)
+        );
+        assert_eq!(
+            rates["E001"].overtime_multiplier,
+            Rate::parse("1.5").unwrap()
+        );
+        let header = "employee_id,hourly_rate,overtime_multiplier,currency\n";
+        assert!(parse_rates(&format!("{header}E001,40,0.5,PLN")).is_err());
+        assert!(parse_rates(&format!("{header}E001,-40,1.5,PLN")).is_err());
+        assert!(parse_rates(&format!("{header}E001,40,1.5,XXX")).is_err());
+        assert!(parse_rates(&format!("{header}E001,40,1.5,PLN\nE001,50,1.5,PLN")).is_err());
+        assert_eq!(
+            parse_rates(&format!("{header}E001,12.345,1.5,EUR")),
+            Err(String::from(
+                "line 2: hourly rate 12.345 has more than 2 decimals"
+            ))
+        );
+        assert!(parse_rates(&format!("{header}E001,1000.5,1.5,JPY")).is_err());
+    }
+
+    #[test]
+    fn splits_daily_and_weekly_overtime() {
+        let stats = stats(&[
+            ("2026-03-02", "06:00", "16:00"),
+            ("2026-03-03", "08:00", "16:00"),
+            ("2026-03-04", "08:00", "16:00"),
+            ("2026-03-05", "08:00", "16:00"),
+            ("2026-03-06", "08:00", "16:00"),
+            ("2026-03-07", "08:00", "12:00"),
+            ("2026-03-09", "08:00", "12:00"),
+        ]);
+
+        let split = split_hours(&stats, Duration::hours(8), false);
+
+        let day = |date: &str| split[&date.parse::<NaiveDate>().unwrap()];
+        assert_eq!(day("2026-03-02"), (Duration::hours(8), Duration::hours(2)));
+        assert_eq!(day("2026-03-06"), (Duration::hours(8), Duration::zero()));
+        assert_eq!(day("2026-03-07"), (Duration::zero(), Duration::hours(4)));
+        assert_eq!(day("2026-03-09"), (Duration::hours(4), Duration::zero()));
+    }
+
+    #[test]
+    fn pays_overtime_with_premium_per_period() {
+        let mut all = BTreeMap::new();
+        all.insert(
+            String::from("E001"),
+            stats(&[
+                ("2026-03-31", "08:00", "17:30"),
+                ("2026-04-01", "08:00", "12:00"),
+            ]),
+        );
+        all.insert(
+            String::from("E003"),
+            stats(&[("2026-03-31", "08:00", "12:00")]),
+        );
+        let rates = parse_rates(RATES).unwrap();
+
+        let payroll = payroll(
+            &all,
+            &rates,
+            Duration::hours(8),
+            false,
+            Period::Month,
+            NaiveDate::MIN..=NaiveDate::MAX,
+        )
+        .unwrap();
+
+        assert_eq!(payroll.missing_rates, vec![String::from("E003")]);
+        let march = &payroll.lines[0];
+        assert_eq!(march.period, "2026-03-01".parse().unwrap());
+        assert_eq!(march.overtime, Duration::minutes(90));
+        assert_eq!(march.regular_pay, Money::from_minor(32_000, Currency::PLN));
+        assert_eq!(march.overtime_pay, Money::from_minor(9_000, Currency::PLN));
+        assert_eq!(march.premium, Money::from_minor(3_000, Currency::PLN));
+        assert_eq!(march.total(), Ok(Money::from_minor(41_000, Currency::PLN)));
+        assert_eq!(
+            payroll.lines[1].total(),
+            Ok(Money::from_minor(16_000, Currency::PLN))
+        );
+        assert_eq!(
+            payroll.totals()[&"2026-04-01".parse().unwrap()].to_string(),
+            "160.00 PLN"
+        );
+    }
+
+    #[test]
+    fn pays_weekly_overtime_of_a_period_starting_mid_week() {
+        let mut all = BTreeMap::new();
+        all.insert(
+            String::from("E001"),
+            stats(&[
+                ("2026-03-02", "08:00", "16:00"),
+                ("2026-03-03", "08:00", "16:00"),
+                ("2026-03-04", "08:00", "16:00"),
+                ("2026-03-05", "08:00", "16:00"),
+                ("2026-03-06", "08:00", "16:00"),
+                ("2026-03-07", "08:00", "12:00"),
+                ("2026-03-09", "08:00", "12:00"),
+            ]),
+        );
+        let rates = parse_rates(RATES).unwrap();
+        let paid_days = "2026-03-07".parse().unwrap()..="2026-03-09".parse().unwrap();
+
+        let payroll = payroll(
+            &all,
+            &rates,
+            Duration::hours(8),
+            false,
+            Period::Week,
+            paid_days,
+        )
+        .unwrap();
+
+        let week = &payroll.lines[0];
+        assert_eq!(payroll.lines.len(), 2);
+        assert_eq!(week.period, "2026-03-02".parse().unwrap());
+        assert_eq!(
+            (week.regular, week.overtime),
+            (Duration::zero(), Duration::hours(4))
+        );
+        assert_eq!(week.total(), Ok(Money::from_minor(24_000, Currency::PLN)));
+        assert_eq!(payroll.lines[1].regular, Duration::hours(4));
+    }
+
+    #[test]
+    fn pays_holiday_hours_as_overtime_only_when_asked() {
+        let holidays = Holidays::parse("2026-04-06,Poniedziałek Wielkanocny").unwrap();
+        let stats = stats_with_holidays(
+            &[
+                ("2026-04-06", "08:00", "14:00"),
+                ("2026-04-07", "08:00", "14:00"),
+            ],
+            &holidays,
+        );
+
+        let split = split_hours(&stats, Duration::hours(8), true);
+
+        assert_eq!(
+            split[&"2026-04-06".parse::<NaiveDate>().unwrap()],
+            (Duration::zero(), Duration::hours(6))
+        );
+        assert_eq!(
+            split[&"2026-04-07".parse::<NaiveDate>().unwrap()],
+            (Duration::hours(6), Duration::zero())
+        );
+        let split = split_hours(&stats, Duration::hours(8), false);
+        assert_eq!(
+            split[&"2026-04-06".parse::<NaiveDate>().unwrap()],
+            (Duration::hours(6), Duration::zero())
+        );
+    }
+}
@@ -0,0 +1,719 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::exercises::money::Locale;
+use crate::exercises::utils::{csv_string, escape_json, escape_markdown};
+
+use super::stats::{EmployeeStats, MIN_REST_HOURS, Violation, WEEKLY_LIMIT_HOURS};
+use super::validation::{Issue, Problem};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Csv,
+    Json,
+    Md,
+}
+
+impl OutputFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Text => "txt",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Json => "json",
+            OutputFormat::Md => "md",
+        }
+    }
+}
+
+/// Language of labels and messages in reports, CSV and JSON keep English keys and codes
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Language {
+    #[default]
+    Pl,
+    En,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+pub enum DurationStyle {
+    /// 7h 30min
+    #[default]
+    Clock,
+    /// 7.50
+    Decimal,
+}
+
+pub struct Labels {
+    pub title: &'static str,
+    pub employee: &'static str,
+    pub total: &'static str,
+    pub days: &'static str,
+    pub average_day: &'static str,
+    pub overtime_days: &'static str,
+    pub weeks: &'static str,
+    pub months: &'static str,
+    pub weekend_work: &'static str,
+    pub holiday_work: &'static str,
+    pub violations: &'static str,
+    pub none: &'static str,
+    pub skipped_rows: &'static str,
+    pub line: &'static str,
+    pub saved: &'static str,
+    pub clocked_in: &'static str,
+    pub clocked_out: &'static str,
+    pub working_since: &'static str,
+    pub nobody_clocked_in: &'static str,
+    pub period_from: &'static str,
+    pub overtime: &'static str,
+    pub premium: &'static str,
+    pub total_pay: &'static str,
+    pub missing_rate: &'static str,
+    pub unreadable_row: &'static str,
+    pub field_count: &'static str,
+    pub missing_employee: &'static str,
+    pub invalid_date: &'static str,
+    pub invalid_time: &'static str,
+    pub invalid_break: &'static str,
+    pub empty_shift: &'static str,
+    pub long_break: &'static str,
+    pub duplicate_of: &'static str,
+    pub overlaps: &'static str,
+    pub cannot_read: &'static str,
+    pub cannot_write: &'static str,
+    pub already_clocked_in: &'static str,
+    pub not_clocked_in: &'static str,
+    pub shift_too_short: &'static str,
+    pub stale_shift: &'static str,
+    pub fix_line: &'static str,
+}
+
+const PL: Labels = Labels {
+    title: "Raport czasu pracy",
+    employee: "Pracownik",
+    total: "Całkowity czas pracy",
+    days: "Dni pracy",
+    average_day: "Średnia długość dnia pracy",
+    overtime_days: "Dni z nadgodzinami",
+    weeks: "Tygodnie",
+    months: "Miesiące",
+    weekend_work: "Praca w weekendy",
+    holiday_work: "Praca w święta",
+    violations: "Naruszenia przepisów",
+    none: "brak",
+    skipped_rows: "Pominięte wiersze",
+    line: "wiersz",
+    saved: "Raport zapisany do",
+    clocked_in: "wejście",
+    clocked_out: "wyjście",
+    working_since: "w pracy od",
+    nobody_clocked_in: "Nikt nie jest w pracy",
+    period_from: "Okres od",
+    overtime: "nadgodzin",
+    premium: "dodatek",
+    total_pay: "Razem",
+    missing_rate: "Brak stawki",
+    unreadable_row: "nieczytelny wiersz",
+    field_count: "nieprawidłowa liczba pól",
+    missing_employee: "brak identyfikatora pracownika",
+    invalid_date: "nieprawidłowa data",
+    invalid_time: "nieprawidłowa godzina",
+    invalid_break: "nieprawidłowa długość przerwy",
+    empty_shift: "zmiana zaczyna się i kończy o",
+    long_break: "przerwa nie jest krótsza niż zmiana",
+    duplicate_of: "powtórzenie wiersza",
+    overlaps: "nakłada się na wiersz",
+    cannot_read: "Nie można odczytać",
+    cannot_write: "Nie można zapisać",
+    already_clocked_in: "już w pracy od",
+    not_clocked_in: "nie jest w pracy",
+    shift_too_short: "zmiana musi trwać co najmniej minutę, wejście o",
+    stale_shift: "w pracy ponad dobę, od",
+    fix_line: "popraw ręcznie wiersz",
+};
+
+const EN: Labels = Labels {
+    title: "Work time report",
+    employee: "Employee",
+    total: "Total work time",
+    days: "Work days",
+    average_day: "Average work day",
+    overtime_days: "Overtime days",
+    weeks: "Weeks",
+    months: "Months",
+    weekend_work: "Weekend work",
+    holiday_work: "Holiday work",
+    violations: "Compliance violations",
+    none: "none",
+    skipped_rows: "Skipped rows",
+    line: "line",
+    saved: "Report saved to",
+    clocked_in: "clocked in",
+    clocked_out: "clocked out",
+    working_since: "working since",
+    nobody_clocked_in: "Nobody is clocked in",
+    period_from: "Period from",
+    overtime: "overtime",
+    premium: "premium",
+    total_pay: "Total",
+    missing_rate: "No rate",
+    unreadable_row: "unreadable row",
+    field_count: "wrong number of fields",
+    missing_employee: "missing employee id",
+    invalid_date: "invalid date",
+    invalid_time: "invalid time",
+    invalid_break: "invalid break minutes",
+    empty_shift: "shift starts and ends at",
+    long_break: "break is not shorter than the shift",
+    duplicate_of: "duplicate of line",
+    overlaps: "overlaps line",
+    cannot_read: "Cannot read",
+    cannot_write: "Cannot write",
+    already_clocked_in: "already clocked in since",
+    not_clocked_in: "not clocked in",
+    shift_too_short: "a shift must last at least a minute, clocked in at",
+    stale_shift: "clocked in for over a day, since",
+    fix_line: "fix by hand line",
+};
+
+impl Language {
+    pub fn labels(&self) -> &'static Labels {
+        match self {
+            Language::Pl => &PL,
+            Language::En => &EN,
+        }
+    }
+
+    pub fn money_locale(&self) -> Locale {
+        match self {
+            Language::Pl => Locale::PlPl,
+            Language::En => Locale::EnUs,
+        }
+    }
+}
+
+impl DurationStyle {
+    /// Duration without a unit in decimal style, for CSV and JSON values
+    pub fn value(&self, duration: Duration) -> String {
+        let minutes = duration.num_minutes();
+        match self {
+            DurationStyle::Clock => format!("{}h {}min", minutes / 60, minutes % 60),
+            DurationStyle::Decimal => format!("{:.2}", minutes as f64 / 60.0),
+        }
+    }
+
+    pub fn format(&self, duration: Duration) -> String {
+        match self {
+            DurationStyle::Clock => self.value(duration),
+            DurationStyle::Decimal => format!("{} h", self.value(duration)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ReportOptions {
+    pub format: OutputFormat,
+    pub language: Language,
+    pub durations: DurationStyle,
+}
+
+struct Report<'a> {
+    stats: &'a BTreeMap<String, EmployeeStats>,
+    issues: &'a [Issue],
+    labels: &'static Labels,
+    language: Language,
+    durations: DurationStyle,
+}
+
+pub fn render(
+    stats: &BTreeMap<String, EmployeeStats>,
+    issues: &[Issue],
+    options: ReportOptions,
+) -> Result<String, String> {
+    let report = Report {
+        stats,
+        issues,
+        labels: options.language.labels(),
+        language: options.language,
+        durations: options.durations,
+    };
+    let output = match options.format {
+        OutputFormat::Text => to_text(&report),
+        OutputFormat::Csv => to_csv(&report)?,
+        OutputFormat::Json => to_json(&report),
+        OutputFormat::Md => to_markdown(&report),
+    };
+    Ok(output)
+}
+
+impl Report<'_> {
+    fn value(&self, duration: Duration) -> String {
+        self.durations.value(duration)
+    }
+
+    fn duration(&self, duration: Duration) -> String {
+        self.durations.format(duration)
+    }
+
+    fn violation(&self, violation: &Violation) -> String {
+        match (violation, self.language) {
+            (Violation::WeeklyOvertime { week, worked }, Language::Pl) => format!(
+                "tydzień od {week}: przepracowano {}, limit to {WEEKLY_LIMIT_HOURS}h",
+                self.duration(*worked)
+            ),
+            (Violation::WeeklyOvertime { week, worked }, Language::En) => format!(
+                "week of {week}: {} worked, limit is {WEEKLY_LIMIT_HOURS}h",
+                self.duration(*worked)
+            ),
+            (Violation::ShortRest { from, to }, Language::Pl) => format!(
+                "odpoczynek od {from} do {to} trwał {}, minimum to {MIN_REST_HOURS}h",
+                self.duration(*to - *from)
+            ),
+            (Violation::ShortRest { from, to }, Language::En) => format!(
+                "rest from {from} to {to} lasted {}, minimum is {MIN_REST_HOURS}h",
+                self.duration(*to - *from)
+            ),
+        }
+    }
+
+    fn problem(&self, problem: &Problem) -> String {
+        let labels = self.labels;
+        match problem {
+            Problem::Unreadable(error) => format!("{}: {error}", labels.unreadable_row),
+            Problem::FieldCount(found) => format!("{}: {found}", labels.field_count),
+            Problem::MissingEmployee => labels.missing_employee.to_string(),
+            Problem::InvalidDate(value) => format!("{}: {value}", labels.invalid_date),
+            Problem::InvalidTime(value) => format!("{}: {value}", labels.invalid_time),
+            Problem::InvalidBreak(value) => format!("{}: {value}", labels.invalid_break),
+            Problem::EmptyShift(time) => format!("{} {}", labels.empty_shift, time.format("%H:%M")),
+            Problem::LongBreak(minutes) => format!("{}: {minutes} min", labels.long_break),
+            Problem::Duplicate(line) => format!("{} {line}", labels.duplicate_of),
+            Problem::Overlap(line) => format!("{} {line}", labels.overlaps),
+        }
+    }
+
+    fn issue(&self, issue: &Issue) -> String {
+        format!(
+            "{} {}: {}",
+            self.labels.line,
+            issue.line,
+            self.problem(&issue.problem)
+        )
+    }
+
+    fn violations(&self) -> impl Iterator<Item = (&String, &Violation)> {
+        self.stats.iter().flat_map(|(employee, stats)| {
+            stats
+                .violations
+                .iter()
+                .map(move |violation| (employee, violation))
+        })
+    }
+}
+
+fn join_or(values: Vec<String>, empty: &str) -> String {
+    if values.is_empty() {
+        empty.to_string()
+    } else {
+        values.join(", ")
+    }
+}
+
+fn dates(dates: &[NaiveDate]) -> Vec<String> {
+    dates.iter().map(NaiveDate::to_string).collect()
+}
+
+fn holidays(days: &[(NaiveDate, String)]) -> Vec<String> {
+    days.iter()
+        .map(|(date, name)| match name.is_empty() {
+            true => date.to_string(),
+            false => format!("{date} ({name})"),
+        })
+        .collect()
+}
+
+fn to_text(report: &Report) -> String {
+    let labels = report.labels;
+    let mut output = String::new();
+    for (employee, stats) in report.stats {
+        let _ = writeln!(output, "{}: {employee}", labels.employee);
+        let _ = writeln!(
+            output,
+            "  {}: {}",
+            labels.total,
+            report.duration(stats.total)
+        );
+        let _ = writeln!(output, "  {}: {}", labels.days, stats.days());
+        let _ = writeln!(
+            output,
+            "  {}: {}",
+            labels.average_day,
+            report.duration(stats.average_day())
+        );
+        let _ = writeln!(
+            output,
+            "  {}: {}",
+            labels.overtime_days,
+            join_or(dates(&stats.overtime_days), "-")
+        );
+        let _ = writeln!(output, "  {}:", labels.weeks);
+        for (week, worked) in &stats.weekly {
+            let _ = writeln!(output, "    {week}: {}", report.duration(*worked));
+        }
+        let _ = writeln!(output, "  {}:", labels.months);
+        for (month, worked) in &stats.monthly {
+            let _ = writeln!(
+                output,
+                "    {}: {}",
+                month.format("%Y-%m"),
+                report.duration(*worked)
+            );
+        }
+        let _ = writeln!(
+            output,
+            "  {}: {}",
+            labels.weekend_work,
+            join_or(dates(&stats.weekend_days), "-")
+        );
+        let _ = writeln!(
+            output,
+            "  {}: {}",
+            labels.holiday_work,
+            join_or(holidays(&stats.holiday_days), "-")
+        );
+        output.push('\n');
+    }
+
+    let _ = writeln!(output, "{}:", labels.violations);
+    let mut violations = report.violations().peekable();
+    if violations.peek().is_none() {
+        let _ = writeln!(output, "  {}", labels.none);
+    }
+    for (employee, violation) in violations {
+        let _ = writeln!(output, "  {employee}: {}", report.violation(violation));
+    }
+
+    if !report.issues.is_empty() {
+        let _ = writeln!(output, "\n{}: {}", labels.skipped_rows, report.issues.len());
+        for issue in report.issues {
+            let _ = writeln!(output, "  {}", report.issue(issue));
+        }
+    }
+    output
+}
+
+/// One row per employee and period: the whole range, each day, week and month
+fn to_csv(report: &Report) -> Result<String, String> {
+    let mut records = Vec::new();
+    records.push(vec![
+        "employee".into(),
+        "period".into(),
+        "start".into(),
+        "worked".into(),
+        "flags".into(),
+    ]);
+    for (employee, stats) in report.stats {
+        records.push(vec![
+            employee.clone(),
+            "total".into(),
+            String::new(),
+            report.value(stats.total),
+            String::new(),
+        ]);
+        for (day, worked) in &stats.daily {
+            let flags = [
+                ("overtime", stats.overtime_days.contains(day)),
+                ("weekend", stats.weekend_days.contains(day)),
+                (
+                    "holiday",
+                    stats.holiday_days.iter().any(|(date, _)| date == day),
+                ),
+            ];
+            let flags: Vec<&str> = flags
+                .into_iter()
+                .filter(|(_, set)| *set)
+                .map(|(flag, _)| flag)
+                .collect();
+            records.push(vec![
+                employee.clone(),
+                "day".into(),
+                day.to_string(),
+                report.value(*worked),
+                flags.join(" "),
+            ]);
+        }
+        for (week, worked) in &stats.weekly {
+            records.push(vec![
+                employee.clone(),
+                "week".into(),
+                week.to_string(),
+                report.value(*worked),
+                String::new(),
+            ]);
+        }
+        for (month, worked) in &stats.monthly {
+            records.push(vec![
+                employee.clone(),
+                "month".into(),
+                month.to_string(),
+                report.value(*worked),
+                String::new(),
+            ]);
+        }
+    }
+    csv_string(&records)
+}
+
+fn to_json(report: &Report) -> String {
+    let duration = |duration: Duration| match report.durations {
+        DurationStyle::Clock => format!("\"{}\"", report.value(duration)),
+        DurationStyle::Decimal => report.value(duration),
+    };
+    let quoted = |values: Vec<String>| {
+        values
+            .iter()
+            .map(|value| format!("\"{}\"", escape_json(value)))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    let periods = |periods: &BTreeMap<NaiveDate, Duration>, format: &str| {
+        periods
+            .iter()
+            .map(|(start, worked)| format!("\"{}\": {}", start.format(format), duration(*worked)))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    let employees: Vec<String> = report
+        .stats
+        .iter()
+        .map(|(employee, stats)| {
+            let holidays: Vec<String> = stats
+                .holiday_days
+                .iter()
+                .map(|(date, name)| {
+                    format!("{{\"date\": \"{date}\", \"name\": \"{}\"}}", escape_json(name))
+                })
+                .collect();
+            let violations: Vec<String> = stats
+                .violations
+                .iter()
+                .map(|violation| match violation {
+                    Violation::WeeklyOvertime { week, worked } => format!(
+                        "{{\"type\": \"weekly_overtime\", \"week\": \"{week}\", \"worked\": {}}}",
+                        duration(*worked)
+                    ),
+                    Violation::ShortRest { from, to } => format!(
+                        "{{\"type\": \"short_rest\", \"from\": \"{}\", \"to\": \"{}\", \"rest\": {}}}",
+                        from.format("%Y-%m-%dT%H:%M"),
+                        to.format("%Y-%m-%dT%H:%M"),
+                        duration(*to - *from)
+                    ),
+                })
+                .collect();
+            format!(
+                "    {{\"employee\": \"{}\", \"total\": {}, \"days\": {}, \"shifts\": {}, \"average_day\": {}, \"overtime_days\": [{}], \"weekly\": {{{}}}, \"monthly\": {{{}}}, \"weekend_days\": [{}], \"holidays\": [{}], \"violations\": [{}]}}",
+                escape_json(employee),
+                duration(stats.total),
+                stats.days(),
+                stats.shifts,
+                duration(stats.average_day()),
+                quoted(dates(&stats.overtime_days)),
+                periods(&stats.weekly, "%Y-%m-%d"),
+                periods(&stats.monthly, "%Y-%m"),
+                quoted(dates(&stats.weekend_days)),
+                holidays.join(", "),
+                violations.join(", ")
+            )
+        })
+        .collect();
+    let issues: Vec<String> = report
+        .issues
+        .iter()
+        .map(|issue| {
+            format!(
+                "    {{\"line\": {}, \"type\": \"{}\", \"message\": \"{}\"}}",
+                issue.line,
+                issue.problem.code(),
+                escape_json(&report.problem(&issue.problem))
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"employees\": [\n{}\n  ],\n  \"skipped_rows\": [\n{}\n  ]\n}}\n",
+        employees.join(",\n"),
+        issues.join(",\n")
+    )
+}
+
+fn to_markdown(report: &Report) -> String {
+    let labels = report.labels;
+    let mut output = format!("# {}\n\n", labels.title);
+    let _ = writeln!(
+        output,
+        "| {} | {} | {} | {} | {} |",
+        labels.employee, labels.total, labels.days, labels.average_day, labels.overtime_days
+    );
+    output.push_str("|---|---:|---:|---:|---|\n");
+    for (employee, stats) in report.stats {
+        let _ = writeln!(
+            output,
+            "| {} | {} | {} | {} | {} |",
+            escape_markdown(employee),
+            report.duration(stats.total),
+            stats.days(),
+            report.duration(stats.average_day()),
+            join_or(dates(&stats.overtime_days), "-")
+        );
+    }
+
+    for (employee, stats) in report.stats {
+        let _ = writeln!(output, "\n## {}\n", escape_markdown(employee));
+        let _ = writeln!(output, "| {} | {} |", labels.weeks, labels.total);
+        output.push_str("|---|---:|\n");
+        for (week, worked) in &stats.weekly {
+            let _ = writeln!(output, "| {week} | {} |", report.duration(*worked));
+        }
+        let _ = writeln!(output, "\n| {} | {} |", labels.months, labels.total);
+        output.push_str("|---|---:|\n");
+        for (month, worked) in &stats.monthly {
+            let _ = writeln!(
+                output,
+                "| {} | {} |",
+                month.format("%Y-%m"),
+                report.duration(*worked)
+            );
+        }
+        let _ = writeln!(
+            output,
+            "\n- {}: {}",
+            labels.weekend_work,
+            join_or(dates(&stats.weekend_days), labels.none)
+        );
+        let _ = writeln!(
+            output,
+            "- {}: {}",
+            labels.holiday_work,
+            escape_markdown(&join_or(holidays(&stats.holiday_days), labels.none))
+        );
+    }
+
+    let _ = writeln!(output, "\n## {}\n", labels.violations);
+    let mut violations = report.violations().peekable();
+    if violations.peek().is_none() {
+        let _ = writeln!(output, "{}", labels.none);
+    }
+    for (employee, violation) in violations {
+        let _ = writeln!(
+            output,
+            "- **{}**: {}",
+            escape_markdown(employee),
+            report.violation(violation)
+        );
+    }
+
+    if !report.issues.is_empty() {
+        let _ = writeln!(output, "\n## {}\n", labels.skipped_rows);
+        for issue in report.issues {
+            let _ = writeln!(output, "- {}", escape_markdown(&report.issue(issue)));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exercises::employees::WorkEntry;
+    use crate::exercises::employees::holidays::Holidays;
+
+    fn stats() -> BTreeMap<String, EmployeeStats> {
+        let entry = |employee: &str, date: &str, start: &str, end: &str| WorkEntry {
+            line: 0,
+            employee_id: employee.to_string(),
+            date: date.parse().unwrap(),
+            start_time: start.parse().unwrap(),
+            end_time: end.parse().unwrap(),
+            break_duration: Duration::zero(),
+        };
+        let second = [entry("E002", "2026-03-07", "08:00", "15:30")];
+        let first = [
+            entry("E001", "2026-03-02", "08:00", "17:00"),
+            entry("E001", "2026-03-03", "03:00", "06:00"),
+        ];
+        let mut stats = BTreeMap::new();
+        for shifts in [&second[..], &first[..]] {
+            let shifts: Vec<&WorkEntry> = shifts.iter().collect();
+            stats.insert(
+                shifts[0].employee_id.clone(),
+                EmployeeStats::from_shifts(&shifts, Duration::hours(8), &Holidays::default()),
+            );
+        }
+        stats
+    }
+
+    fn render_with(format: OutputFormat, language: Language, durations: DurationStyle) -> String {
+        let issues = [
+            Issue {
+                line: 4,
+                problem: Problem::InvalidTime(String::from("8am")),
+            },
+            Issue {
+                line: 6,
+                problem: Problem::Overlap(5),
+            },
+        ];
+        let options = ReportOptions {
+            format,
+            language,
+            durations,
+        };
+        render(&stats(), &issues, options).unwrap()
+    }
+
+    #[test]
+    fn text_report_is_sorted_and_localised() {
+        let polish = render_with(OutputFormat::Text, Language::Pl, DurationStyle::Clock);
+        let english = render_with(OutputFormat::Text, Language::En, DurationStyle::Decimal);
+
+        assert!(polish.find("E001").unwrap() < polish.find("E002").unwrap());
+        assert!(polish.contains("Całkowity czas pracy: 12h 0min"));
+        assert!(polish.contains("Średnia długość dnia pracy: 6h 0min"));
+        assert!(polish.contains("odpoczynek od 2026-03-02 17:00:00 do 2026-03-03 03:00:00"));
+        assert!(polish.contains("wiersz 4: nieprawidłowa godzina: 8am"));
+        assert!(polish.contains("wiersz 6: nakłada się na wiersz 5"));
+        assert!(english.contains("Total work time: 12.00 h"));
+        assert!(english.contains("Weekend work: 2026-03-07"));
+        assert!(english.contains("line 4: invalid time: 8am"));
+    }
+
+    #[test]
+    fn csv_report_lists_periods_with_flags() {
+        let csv = render_with(OutputFormat::Csv, Language::En, DurationStyle::Decimal);
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "employee,period,start,worked,flags");
+        assert_eq!(lines[1], "E001,total,,12.00,");
+        assert_eq!(lines[2], "E001,day,2026-03-02,9.00,overtime");
+        assert!(lines.contains(&"E002,day,2026-03-07,7.50,weekend"));
+        assert!(lines.contains(&"E001,month,2026-03-01,12.00,"));
+    }
+
+    #[test]
+    fn json_and_markdown_reports() {
+        let json = render_with(OutputFormat::Json, Language::Pl, DurationStyle::Decimal);
+        let markdown = render_with(OutputFormat::Md, Language::En, DurationStyle::Clock);
+
+        assert!(json.contains("\"employee\": \"E001\", \"total\": 12.00"));
+        assert!(json.contains("\"type\": \"short_rest\""));
+        assert!(json.contains(
+            "{\"line\": 4, \"type\": \"invalid_time\", \"message\": \"nieprawidłowa godzina: 8am\"}"
+        ));
+        assert!(markdown.contains("- line 6: overlaps line 5\n"));
+        assert!(markdown.starts_with("# Work time report\n"));
+        assert!(markdown.contains("| E001 | 12h 0min | 2 | 6h 0min | 2026-03-02 |"));
+        assert!(markdown.contains("## Compliance violations"));
+    }
+}
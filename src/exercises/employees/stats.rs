@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
+
+use super::WorkEntry;
+use super::holidays::Holidays;
+
+pub const WEEKLY_LIMIT_HOURS: i64 = 40;
+pub const MIN_REST_HOURS: i64 = 11;
+
+#[derive(Debug, PartialEq)]
+pub enum Violation {
+    /// More than `WEEKLY_LIMIT_HOURS` in the week starting on the date
+    WeeklyOvertime { week: NaiveDate, worked: Duration },
+    /// Less than `MIN_REST_HOURS` between the end of one work day and the start of the next
+    ShortRest {
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    },
+}
+
+/// Hours of one employee, shifts are counted towards the day, week and month they start in
+#[derive(Debug, Default)]
+pub struct EmployeeStats {
+    pub total: Duration,
+    pub shifts: usize,
+    pub daily: BTreeMap<NaiveDate, Duration>,
+    /// Keyed by the Monday of each week
+    pub weekly: BTreeMap<NaiveDate, Duration>,
+    /// Keyed by the first day of each month
+    pub monthly: BTreeMap<NaiveDate, Duration>,
+    pub overtime_days: Vec<NaiveDate>,
+    pub weekend_days: Vec<NaiveDate>,
+    pub holiday_days: Vec<(NaiveDate, String)>,
+    pub violations: Vec<Violation>,
+}
+
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("First day always exists")
+}
+
+impl EmployeeStats {
+    /// Aggregates the shifts of a single employee
+    pub fn from_shifts(
+        shifts: &[&WorkEntry],
+        overtime_threshold: Duration,
+        holidays: &Holidays,
+    ) -> Self {
+        let mut stats = EmployeeStats::default();
+        for entry in shifts {
+            let worked = entry.worked();
+            stats.total += worked;
+            stats.shifts += 1;
+            *stats.daily.entry(entry.date).or_insert_with(Duration::zero) += worked;
+            *stats
+                .weekly
+                .entry(week_start(entry.date))
+                .or_insert_with(Duration::zero) += worked;
+            *stats
+                .monthly
+                .entry(month_start(entry.date))
+                .or_insert_with(Duration::zero) += worked;
+        }
+
+        for (date, worked) in &stats.daily {
+            if *worked > overtime_threshold {
+                stats.overtime_days.push(*date);
+            }
+            if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+                stats.weekend_days.push(*date);
+            }
+            if let Some(name) = holidays.get(*date) {
+                stats.holiday_days.push((*date, name.to_string()));
+            }
+        }
+
+        for (week, worked) in &stats.weekly {
+            if *worked > Duration::hours(WEEKLY_LIMIT_HOURS) {
+                stats.violations.push(Violation::WeeklyOvertime {
+                    week: *week,
+                    worked: *worked,
+                });
+            }
+        }
+        stats.violations.extend(short_rests(shifts));
+        stats
+    }
+
+    pub fn days(&self) -> usize {
+        self.daily.len()
+    }
+
+    pub fn average_day(&self) -> Duration {
+        match self.days() {
+            0 => Duration::zero(),
+            days => self.total / days as i32,
+        }
+    }
+}
+
+// Daily rest is checked between work days, gaps between shifts of the same day are breaks
+fn short_rests(shifts: &[&WorkEntry]) -> Vec<Violation> {
+    let mut days: BTreeMap<NaiveDate, (NaiveDateTime, NaiveDateTime)> = BTreeMap::new();
+    for entry in shifts {
+        let day = days
+            .entry(entry.date)
+            .or_insert((entry.start(), entry.end()));
+        day.0 = day.0.min(entry.start());
+        day.1 = day.1.max(entry.end());
+    }
+    days.values()
+        .zip(days.values().skip(1))
+        .filter(|((_, end), (start, _))| *start - *end < Duration::hours(MIN_REST_HOURS))
+        .map(|((_, end), (start, _))| Violation::ShortRest {
+            from: *end,
+            to: *start,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(date: &str, start_time: &str, end_time: &str) -> WorkEntry {
+        WorkEntry {
+            line: 0,
+            employee_id: String::from("E001"),
+            date: date.parse().unwrap(),
+            start_time: start_time.parse().unwrap(),
+            end_time: end_time.parse().unwrap(),
+            break_duration: Duration::zero(),
+        }
+    }
+
+    fn stats(entries: &[WorkEntry], holidays: &Holidays) -> EmployeeStats {
+        let shifts: Vec<&WorkEntry> = entries.iter().collect();
+        EmployeeStats::from_shifts(&shifts, Duration::hours(8), holidays)
+    }
+
+    #[test]
+    fn totals_per_day_week_and_month() {
+        let entries = [
+            entry("2026-03-30", "08:00", "12:00"),
+            entry("2026-03-30", "13:00", "18:00"),
+            entry("2026-04-01", "22:00", "06:00"),
+        ];
+
+        let stats = stats(&entries, &Holidays::default());
+
+        assert_eq!(stats.total, Duration::hours(17));
+        assert_eq!(stats.shifts, 3);
+        assert_eq!(stats.days(), 2);
+        assert_eq!(stats.average_day(), Duration::minutes(8 * 60 + 30));
+        assert_eq!(
+            stats.weekly.get(&"2026-03-30".parse().unwrap()),
+            Some(&Duration::hours(17))
+        );
+        assert_eq!(stats.monthly.len(), 2);
+        assert_eq!(stats.overtime_days, vec!["2026-03-30".parse().unwrap()]);
+        assert!(stats.violations.is_empty());
+    }
+
+    #[test]
+    fn reports_weekly_overtime_and_short_rest() {
+        let entries = [
+            entry("2026-03-02", "06:00", "16:00"),
+            entry("2026-03-03", "06:00", "16:00"),
+            entry("2026-03-04", "06:00", "16:00"),
+            entry("2026-03-05", "06:00", "16:00"),
+            entry("2026-03-05", "18:00", "22:00"),
+            entry("2026-03-06", "06:00", "08:00"),
+        ];
+
+        let stats = stats(&entries, &Holidays::default());
+
+        assert_eq!(
+            stats.violations,
+            vec![
+                Violation::WeeklyOvertime {
+                    week: "2026-03-02".parse().unwrap(),
+                    worked: Duration::hours(46),
+                },
+                Violation::ShortRest {
+                    from: "2026-03-05T22:00:00".parse().unwrap(),
+                    to: "2026-03-06T06:00:00".parse().unwrap(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_weekend_and_holiday_work() {
+        let holidays = Holidays::parse("2026-05-01,Święto Pracy").unwrap();
+        let entries = [
+            entry("2026-05-01", "08:00", "12:00"),
+            entry("2026-05-02", "08:00", "12:00"),
+            entry("2026-05-04", "08:00", "12:00"),
+        ];
+
+        let stats = stats(&entries, &holidays);
+
+        assert_eq!(stats.weekend_days, vec!["2026-05-02".parse().unwrap()]);
+        assert_eq!(
+            stats.holiday_days,
+            vec![("2026-05-01".parse().unwrap(), String::from("Święto Pracy"))]
+        );
+    }
+}
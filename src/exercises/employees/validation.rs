@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate, NaiveTime};
+use csv::StringRecord;
+
+use super::WorkEntry;
+
+const MIN_FIELDS_COUNT: usize = 4;
+const FIELDS_COUNT: usize = 5;
+
+/// Row that was left out of the analysis, `line` counts from 1 with the header as line 1
+#[derive(Debug, PartialEq)]
+pub struct Issue {
+    pub line: u64,
+    pub problem: Problem,
+}
+
+/// Why a row was left out, the report describes it in its own language
+#[derive(Debug, PartialEq)]
+pub enum Problem {
+    /// The row could not be read as CSV, with the reader's error
+    Unreadable(String),
+    FieldCount(usize),
+    MissingEmployee,
+    InvalidDate(String),
+    InvalidTime(String),
+    InvalidBreak(String),
+    EmptyShift(NaiveTime),
+    /// Break in minutes that is not shorter than the shift
+    LongBreak(u32),
+    /// Same shift as the given line
+    Duplicate(u64),
+    /// Shift of the same employee overlapping the given line
+    Overlap(u64),
+}
+
+impl Problem {
+    /// Stable English identifier of the problem, for machine readable reports
+    pub fn code(&self) -> &'static str {
+        match self {
+            Problem::Unreadable(_) => "unreadable",
+            Problem::FieldCount(_) => "field_count",
+            Problem::MissingEmployee => "missing_employee",
+            Problem::InvalidDate(_) => "invalid_date",
+            Problem::InvalidTime(_) => "invalid_time",
+            Problem::InvalidBreak(_) => "invalid_break",
+            Problem::EmptyShift(_) => "empty_shift",
+            Problem::LongBreak(_) => "long_break",
+            Problem::Duplicate(_) => "duplicate",
+            Problem::Overlap(_) => "overlap",
+        }
+    }
+}
+
+/// Reads `employee_id,date,start_time,end_time[,break_minutes]`,
+/// an end time not after the start time means the shift ends on the next day
+pub fn parse_record(record: &StringRecord, line: u64) -> Result<WorkEntry, Problem> {
+    if !(MIN_FIELDS_COUNT..=FIELDS_COUNT).contains(&record.len()) {
+        return Err(Problem::FieldCount(record.len()));
+    }
+    let employee_id = record[0].trim();
+    if employee_id.is_empty() {
+        return Err(Problem::MissingEmployee);
+    }
+    let date = NaiveDate::parse_from_str(record[1].trim(), "%Y-%m-%d")
+        .map_err(|_| Problem::InvalidDate(record[1].to_string()))?;
+    let time = |field: &str| {
+        NaiveTime::parse_from_str(field.trim(), "%H:%M")
+            .map_err(|_| Problem::InvalidTime(field.to_string()))
+    };
+    let start_time = time(&record[2])?;
+    let end_time = time(&record[3])?;
+    let break_minutes = match record.get(4).map(str::trim) {
+        None | Some("") => 0,
+        Some(field) => field
+            .parse::<u32>()
+            .map_err(|_| Problem::InvalidBreak(field.to_string()))?,
+    };
+
+    let entry = WorkEntry {
+        line,
+        employee_id: employee_id.to_string(),
+        date,
+        start_time,
+        end_time,
+        break_duration: Duration::minutes(break_minutes as i64),
+    };
+    if start_time == end_time {
+        return Err(Problem::EmptyShift(start_time));
+    }
+    if entry.worked() <= Duration::zero() {
+        return Err(Problem::LongBreak(break_minutes));
+    }
+    Ok(entry)
+}
+
+/// Drops exact duplicates and shifts overlapping an earlier shift of the same employee
+pub fn validate(entries: Vec<WorkEntry>) -> (Vec<WorkEntry>, Vec<Issue>) {
+    let mut by_employee: HashMap<String, Vec<WorkEntry>> = HashMap::new();
+    for entry in entries {
+        by_employee
+            .entry(entry.employee_id.clone())
+            .or_default()
+            .push(entry);
+    }
+
+    let mut valid = Vec::new();
+    let mut issues = Vec::new();
+    for mut shifts in by_employee.into_values() {
+        shifts.sort_by_key(|entry| (entry.start(), entry.line));
+        let mut previous: Option<(u64, chrono::NaiveDateTime, chrono::NaiveDateTime)> = None;
+        for entry in shifts {
+            if let Some((line, start, end)) = previous {
+                if entry.start() == start && entry.end() == end {
+                    issues.push(Issue {
+                        line: entry.line,
+                        problem: Problem::Duplicate(line),
+                    });
+                    continue;
+                }
+                if entry.start() < end {
+                    issues.push(Issue {
+                        line: entry.line,
+                        problem: Problem::Overlap(line),
+                    });
+                    continue;
+                }
+            }
+            previous = Some((entry.line, entry.start(), entry.end()));
+            valid.push(entry);
+        }
+    }
+
+    valid.sort_by_key(|entry| entry.line);
+    issues.sort_by_key(|issue| issue.line);
+    (valid, issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(fields: &[&str]) -> Result<WorkEntry, Problem> {
+        parse_record(&StringRecord::from(fields.to_vec()), 2)
+    }
+
+    fn lines(entries: &[WorkEntry]) -> Vec<u64> {
+        entries.iter().map(|entry| entry.line).collect()
+    }
+
+    #[test]
+    fn parses_overnight_shifts_and_breaks() {
+        let night = parse(&["E001", "2026-03-02", "22:00", "06:00", "30"]).unwrap();
+        let day = parse(&["E001", "2026-03-02", "08:00", "16:00"]).unwrap();
+
+        assert_eq!(night.end().date(), "2026-03-03".parse().unwrap());
+        assert_eq!(night.worked(), Duration::minutes(7 * 60 + 30));
+        assert_eq!(day.worked(), Duration::hours(8));
+    }
+
+    #[test]
+    fn rejects_invalid_rows() {
+        let problem = |fields: &[&str]| parse(fields).unwrap_err();
+
+        assert_eq!(
+            problem(&["E001", "2026-03-02", "08:00"]),
+            Problem::FieldCount(3)
+        );
+        assert_eq!(
+            problem(&["", "2026-03-02", "08:00", "16:00"]),
+            Problem::MissingEmployee
+        );
+        assert_eq!(
+            problem(&["E001", "2026-02-30", "08:00", "16:00"]),
+            Problem::InvalidDate(String::from("2026-02-30"))
+        );
+        assert_eq!(
+            problem(&["E001", "2026-03-02", "8am", "16:00"]),
+            Problem::InvalidTime(String::from("8am"))
+        );
+        assert_eq!(
+            problem(&["E001", "2026-03-02", "08:00", "08:00"]),
+            Problem::EmptyShift("08:00".parse().unwrap())
+        );
+        assert_eq!(
+            problem(&["E001", "2026-03-02", "08:00", "09:00", "60"]),
+            Problem::LongBreak(60)
+        );
+        assert_eq!(
+            problem(&["E001", "2026-03-02", "08:00", "16:00", "-5"]),
+            Problem::InvalidBreak(String::from("-5"))
+        );
+    }
+
+    #[test]
+    fn reports_duplicates_and_overlaps_per_employee() {
+        let rows = [
+            ["E001", "2026-03-02", "22:00", "06:00"],
+            ["E001", "2026-03-03", "05:00", "09:00"],
+            ["E002", "2026-03-03", "05:00", "09:00"],
+            ["E001", "2026-03-02", "22:00", "06:00"],
+            ["E001", "2026-03-03", "06:00", "10:00"],
+        ];
+        let entries = rows
+            .iter()
+            .enumerate()
+            .map(|(index, fields)| {
+                parse_record(&StringRecord::from(fields.to_vec()), index as u64 + 2).unwrap()
+            })
+            .collect();
+
+        let (valid, issues) = validate(entries);
+
+        assert_eq!(lines(&valid), vec![2, 4, 6]);
+        assert_eq!(
+            issues,
+            vec![
+                Issue {
+                    line: 3,
+                    problem: Problem::Overlap(2)
+                },
+                Issue {
+                    line: 5,
+                    problem: Problem::Duplicate(2)
+                },
+            ]
+        );
+    }
+}
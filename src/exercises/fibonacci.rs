@@ -1,3 +1,23 @@
+mod big_uint;
+mod recurrence;
+
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use clap::{Parser, Subcommand};
+
+pub use big_uint::BigUint;
+pub use recurrence::LinearRecurrence;
+
+/// Largest n whose Fibonacci number fits in `u64`
+const MAX_U64_INDEX: u32 = 93;
+/// The exponential version takes seconds above this
+const MAX_RECURSIVE_INDEX: u32 = 35;
+/// Largest index printed exactly by `sequence --term`, larger ones need --mod
+const MAX_EXACT_TERM_INDEX: u64 = u32::MAX as u64;
+const BENCHMARK_RUNS: u32 = 5;
+
 fn fibonacci_recursive(n: u32) -> u64 {
     match n {
         0 => 0,
@@ -24,7 +44,309 @@ fn fibonacci(n: u32) -> u64 {
     }
 }
 
+/// `None` when the number does not fit in `u64`, that is for n above 93
+pub fn checked_fibonacci(n: u32) -> Option<u64> {
+    if n == 0 {
+        return Some(0);
+    }
+    let mut last: u64 = 0;
+    let mut current: u64 = 1;
+    for _ in 1..n {
+        (last, current) = (current, last.checked_add(current)?);
+    }
+    Some(current)
+}
+
+/// Recursive version that computes every smaller number once, `None` above n = 93
+pub fn fibonacci_memoized(n: u32) -> Option<u64> {
+    fn fibonacci(n: u32, memo: &mut HashMap<u32, u64>) -> u64 {
+        if n < 2 {
+            return n as u64;
+        }
+        if let Some(value) = memo.get(&n) {
+            return *value;
+        }
+        let value = fibonacci(n - 1, memo) + fibonacci(n - 2, memo);
+        memo.insert(n, value);
+        value
+    }
+
+    (n <= MAX_U64_INDEX).then(|| fibonacci(n, &mut HashMap::new()))
+}
+
+/// n additions of growing numbers
+pub fn fibonacci_big(n: u32) -> BigUint {
+    let mut last = BigUint::zero();
+    let mut current = BigUint::one();
+    for _ in 0..n {
+        let next = &last + &current;
+        last = current;
+        current = next;
+    }
+    last
+}
+
+/// O(log n) multiplications using F(2k) = F(k) * (2 * F(k + 1) - F(k))
+/// and F(2k + 1) = F(k)^2 + F(k + 1)^2, going through the bits of n from the highest
+pub fn fibonacci_fast_doubling(n: u32) -> BigUint {
+    // F(k) and F(k + 1) for k being the bits of n seen so far
+    let mut current = BigUint::zero();
+    let mut next = BigUint::one();
+    for bit in (0..u32::BITS - n.leading_zeros()).rev() {
+        let double = &current * &(&(&next + &next) - &current);
+        let double_next = &(&current * &current) + &(&next * &next);
+        if n >> bit & 1 == 1 {
+            next = &double + &double_next;
+            current = double_next;
+        } else {
+            current = double;
+            next = double_next;
+        }
+    }
+    current
+}
+
+/// Fibonacci numbers from F(0) that fit in `u64`, the iterator ends instead of overflowing
+#[derive(Debug)]
+pub struct Fibonacci {
+    current: Option<u64>,
+    next: Option<u64>,
+}
+
+impl Fibonacci {
+    pub fn new() -> Fibonacci {
+        Fibonacci {
+            current: Some(0),
+            next: Some(1),
+        }
+    }
+}
+
+impl Default for Fibonacci {
+    fn default() -> Self {
+        Fibonacci::new()
+    }
+}
+
+impl Iterator for Fibonacci {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let current = self.current?;
+        self.current = self.next;
+        self.next = self.next.and_then(|next| next.checked_add(current));
+        Some(current)
+    }
+}
+
+/// Shortest of a few runs, the result is kept from being optimized away
+fn measure<T>(function: impl Fn() -> T) -> Duration {
+    (0..BENCHMARK_RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(function());
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn benchmark(n: u32) {
+    println!("Fibonacci({n}), best of {BENCHMARK_RUNS} runs");
+    let row = |name: &str, time: Option<Duration>| match time {
+        Some(time) => println!("{name:<14} {time:>12.1?}"),
+        None => println!("{name:<14} {:>12}", "-"),
+    };
+    row(
+        "recursive",
+        (n <= MAX_RECURSIVE_INDEX).then(|| measure(|| fibonacci_recursive(black_box(n)))),
+    );
+    row(
+        "iterative",
+        (n <= MAX_U64_INDEX).then(|| measure(|| fibonacci(black_box(n)))),
+    );
+    row("checked", Some(measure(|| checked_fibonacci(black_box(n)))));
+    row(
+        "memoized",
+        Some(measure(|| fibonacci_memoized(black_box(n)))),
+    );
+    row("big", Some(measure(|| fibonacci_big(black_box(n)))));
+    row(
+        "fast doubling",
+        Some(measure(|| fibonacci_fast_doubling(black_box(n)))),
+    );
+}
+
+#[derive(Parser)]
+#[command(name = "fibonacci", about = "Fibonacci numbers")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum SequenceKind {
+    Fibonacci,
+    Lucas,
+    Tribonacci,
+    Pell,
+}
+
+impl SequenceKind {
+    fn recurrence(&self) -> LinearRecurrence {
+        match self {
+            SequenceKind::Fibonacci => LinearRecurrence::fibonacci(),
+            SequenceKind::Lucas => LinearRecurrence::lucas(),
+            SequenceKind::Tribonacci => LinearRecurrence::tribonacci(),
+            SequenceKind::Pell => LinearRecurrence::pell(),
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print Fibonacci number N of any size
+    Term { n: u32 },
+    /// Compare the running time of the implementations for Fibonacci number N,
+    /// the exponential one runs up to 35 and the u64 one up to 93
+    Bench {
+        #[arg(default_value_t = 90)]
+        n: u32,
+    },
+    /// Print the first terms of a sequence, one of them or the terms modulo M
+    Sequence {
+        #[arg(value_enum, default_value_t = SequenceKind::Fibonacci)]
+        kind: SequenceKind,
+        /// Number of terms printed
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: u64,
+        /// Print only term N, counting from 0, terms above 4294967295 need --mod
+        #[arg(short, long)]
+        term: Option<u64>,
+        /// Print the terms modulo M and the period of the sequence modulo M
+        #[arg(short, long = "mod", value_parser = clap::value_parser!(u64).range(1..))]
+        modulus: Option<u64>,
+        /// Own recurrence a(n) = c1 * a(n - 1) + ... + ck * a(n - k) instead of KIND, e.g. 1,1
+        #[arg(long, value_delimiter = ',', requires = "initial")]
+        coefficients: Vec<u64>,
+        /// First k terms of the own recurrence, e.g. 0,1
+        #[arg(long, value_delimiter = ',', requires = "coefficients")]
+        initial: Vec<u64>,
+    },
+}
+
+fn sequence(
+    recurrence: &LinearRecurrence,
+    count: u64,
+    term: Option<u64>,
+    modulus: Option<u64>,
+) -> Result<(), String> {
+    match (term, modulus) {
+        (Some(n), Some(modulus)) => println!("{}", recurrence.term_mod(n, modulus)),
+        (Some(n), None) if n > MAX_EXACT_TERM_INDEX => {
+            return Err(format!(
+                "Term {n} is too large to compute exactly, the limit is {MAX_EXACT_TERM_INDEX} without --mod"
+            ));
+        }
+        // Fibonacci numbers have a faster way than adding up every term before
+        (Some(n), None) if *recurrence == LinearRecurrence::fibonacci() => {
+            println!("{}", fibonacci_fast_doubling(n as u32))
+        }
+        (Some(n), None) => println!("{}", recurrence.term(n)),
+        (None, Some(modulus)) => {
+            for term in recurrence.terms_mod(modulus).take(count as usize) {
+                println!("{term}");
+            }
+            match recurrence.period_mod(modulus) {
+                Some(period) => println!("Period modulo {modulus}: {period}"),
+                None => println!("No period modulo {modulus} found"),
+            }
+        }
+        (None, None) => {
+            for term in recurrence.terms().take(count as usize) {
+                println!("{term}");
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn run() {
-    println!("Fibonacci: {}", fibonacci(10));
-    println!("Fibonacci: {}", fibonacci_recursive(10));
+    match Cli::parse().command {
+        Some(Command::Term { n }) => println!("{}", fibonacci_fast_doubling(n)),
+        Some(Command::Bench { n }) => benchmark(n),
+        Some(Command::Sequence {
+            kind,
+            count,
+            term,
+            modulus,
+            coefficients,
+            initial,
+        }) => {
+            let recurrence = if coefficients.is_empty() {
+                Ok(kind.recurrence())
+            } else {
+                LinearRecurrence::new(coefficients, initial)
+            };
+            if let Err(error) =
+                recurrence.and_then(|recurrence| sequence(&recurrence, count, term, modulus))
+            {
+                println!("Error: {error}");
+            }
+        }
+        None => {
+            println!("Fibonacci: {}", fibonacci(10));
+            println!("Fibonacci: {}", fibonacci_recursive(10));
+            let first: Vec<u64> = Fibonacci::new().take(10).collect();
+            println!("Fibonacci: {first:?}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_versions_stop_at_the_u64_limit() {
+        for n in 0..=MAX_U64_INDEX {
+            assert_eq!(checked_fibonacci(n), Some(fibonacci(n)));
+            assert_eq!(fibonacci_memoized(n), Some(fibonacci(n)));
+        }
+        assert_eq!(checked_fibonacci(93), Some(12_200_160_415_121_876_738));
+        assert_eq!(checked_fibonacci(94), None);
+        assert_eq!(fibonacci_memoized(94), None);
+        assert_eq!(fibonacci_recursive(20), 6_765);
+    }
+
+    #[test]
+    fn iterator_ends_at_the_u64_limit() {
+        let numbers: Vec<u64> = Fibonacci::new().collect();
+
+        assert_eq!(numbers.len(), MAX_U64_INDEX as usize + 1);
+        assert_eq!(numbers[..8], [0, 1, 1, 2, 3, 5, 8, 13]);
+        assert_eq!(numbers.last(), checked_fibonacci(MAX_U64_INDEX).as_ref());
+        assert!(
+            Fibonacci::new()
+                .zip(LinearRecurrence::fibonacci().terms())
+                .all(|(number, term)| term.to_u64() == Some(number))
+        );
+    }
+
+    #[test]
+    fn big_versions_agree_past_the_u64_limit() {
+        for n in 0..=300 {
+            let fast = fibonacci_fast_doubling(n);
+
+            assert_eq!(fast, fibonacci_big(n), "n = {n}");
+            if n <= MAX_U64_INDEX {
+                assert_eq!(fast.to_u64(), checked_fibonacci(n));
+            }
+        }
+        assert_eq!(
+            fibonacci_fast_doubling(100).to_string(),
+            "354224848179261915075"
+        );
+        assert_eq!(fibonacci_fast_doubling(1_000).to_string().len(), 209);
+    }
 }
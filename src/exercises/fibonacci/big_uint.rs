@@ -0,0 +1,184 @@
+use std::fmt::Display;
+use std::ops::{Add, Mul, Sub};
+
+const DECIMAL_BASE: u32 = 1_000_000_000;
+const DECIMAL_BASE_DIGITS: usize = 9;
+
+/// Unsigned integer of any size, stored as base 2^32 digits with the least significant first
+/// and without leading zero digits, so zero has no digits at all
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BigUint {
+    digits: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint::default()
+    }
+
+    pub fn one() -> BigUint {
+        BigUint::from(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.digits[..] {
+            [] => Some(0),
+            [low] => Some(low as u64),
+            [low, high] => Some((high as u64) << 32 | low as u64),
+            _ => None,
+        }
+    }
+
+    fn normalized(mut self) -> BigUint {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+        self
+    }
+
+    // Quotient and remainder of the division by a single digit
+    fn div_rem_digit(&self, divisor: u32) -> (BigUint, u32) {
+        let mut quotient = vec![0; self.digits.len()];
+        let mut remainder = 0u64;
+        for (index, &digit) in self.digits.iter().enumerate().rev() {
+            let value = remainder << 32 | digit as u64;
+            quotient[index] = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+        (BigUint { digits: quotient }.normalized(), remainder as u32)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        BigUint {
+            digits: vec![value as u32, (value >> 32) as u32],
+        }
+        .normalized()
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = if self.digits.len() >= other.digits.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut digits = Vec::with_capacity(long.digits.len() + 1);
+        let mut carry = 0u64;
+        for (index, &digit) in long.digits.iter().enumerate() {
+            let sum = digit as u64 + short.digits.get(index).copied().unwrap_or(0) as u64 + carry;
+            digits.push(sum as u32);
+            carry = sum >> 32;
+        }
+        if carry > 0 {
+            digits.push(carry as u32);
+        }
+        BigUint { digits }
+    }
+}
+
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    /// Panics when `other` is larger, like subtraction on the built-in unsigned types
+    fn sub(self, other: &BigUint) -> BigUint {
+        let mut digits = Vec::with_capacity(self.digits.len());
+        let mut borrow = 0i64;
+        for (index, &digit) in self.digits.iter().enumerate() {
+            let mut difference =
+                digit as i64 - other.digits.get(index).copied().unwrap_or(0) as i64 - borrow;
+            borrow = 0;
+            if difference < 0 {
+                difference += 1 << 32;
+                borrow = 1;
+            }
+            digits.push(difference as u32);
+        }
+        if borrow > 0 || other.digits.len() > self.digits.len() {
+            panic!("attempt to subtract with overflow");
+        }
+        BigUint { digits }.normalized()
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut digits = vec![0u32; self.digits.len() + other.digits.len()];
+        for (i, &left) in self.digits.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &right) in other.digits.iter().enumerate() {
+                // Cannot overflow: (2^32 - 1)^2 + 2 * (2^32 - 1) = 2^64 - 1
+                let product = left as u64 * right as u64 + digits[i + j] as u64 + carry;
+                digits[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            digits[i + other.digits.len()] = carry as u32;
+        }
+        BigUint { digits }.normalized()
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return f.pad("0");
+        }
+        // Groups of nine decimal digits, the least significant first
+        let mut groups = Vec::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (quotient, remainder) = rest.div_rem_digit(DECIMAL_BASE);
+            groups.push(remainder);
+            rest = quotient;
+        }
+        let mut text = groups.pop().unwrap_or_default().to_string();
+        for group in groups.iter().rev() {
+            text.push_str(&format!("{group:0DECIMAL_BASE_DIGITS$}"));
+        }
+        f.pad(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_subtracts_and_multiplies_across_digits() {
+        let max = BigUint::from(u64::MAX);
+        let two_to_64 = &max + &BigUint::one();
+        let two_to_128 = &two_to_64 * &two_to_64;
+
+        assert_eq!(two_to_64.to_string(), "18446744073709551616");
+        assert_eq!(
+            two_to_128.to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        assert_eq!(&two_to_64 - &BigUint::one(), max);
+        assert_eq!(&(&two_to_128 - &two_to_128), &BigUint::zero());
+        assert_eq!(two_to_64.to_u64(), None);
+        assert_eq!(max.to_u64(), Some(u64::MAX));
+        assert_eq!(BigUint::from(1_000_000_000).to_string(), "1000000000");
+        assert_eq!(format!("{:>4}", BigUint::zero()), "   0");
+        assert_eq!(&max * &BigUint::zero(), BigUint::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "subtract with overflow")]
+    fn subtraction_below_zero_panics() {
+        let _ = &BigUint::one() - &BigUint::from(2);
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::VecDeque;
+
+use super::BigUint;
+
+/// Steps tried when looking for the period modulo M before giving up
+const MAX_PERIOD_SEARCH: u64 = 10_000_000;
+
+/// Sequence where a(n) = c1 * a(n - 1) + c2 * a(n - 2) + ... + ck * a(n - k),
+/// starting from the k given terms
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearRecurrence {
+    coefficients: Vec<u64>,
+    initial: Vec<u64>,
+}
+
+impl LinearRecurrence {
+    pub fn new(coefficients: Vec<u64>, initial: Vec<u64>) -> Result<LinearRecurrence, String> {
+        if coefficients.is_empty() || coefficients.len() != initial.len() {
+            return Err(format!(
+                "{} coefficients need as many initial terms, found {}",
+                coefficients.len(),
+                initial.len()
+            ));
+        }
+        Ok(LinearRecurrence {
+            coefficients,
+            initial,
+        })
+    }
+
+    /// 0, 1, 1, 2, 3, 5, ...
+    pub fn fibonacci() -> LinearRecurrence {
+        LinearRecurrence {
+            coefficients: vec![1, 1],
+            initial: vec![0, 1],
+        }
+    }
+
+    /// 2, 1, 3, 4, 7, 11, ...
+    pub fn lucas() -> LinearRecurrence {
+        LinearRecurrence {
+            coefficients: vec![1, 1],
+            initial: vec![2, 1],
+        }
+    }
+
+    /// 0, 0, 1, 1, 2, 4, 7, 13, ...
+    pub fn tribonacci() -> LinearRecurrence {
+        LinearRecurrence {
+            coefficients: vec![1, 1, 1],
+            initial: vec![0, 0, 1],
+        }
+    }
+
+    /// 0, 1, 2, 5, 12, 29, ...
+    pub fn pell() -> LinearRecurrence {
+        LinearRecurrence {
+            coefficients: vec![2, 1],
+            initial: vec![0, 1],
+        }
+    }
+
+    pub fn terms(&self) -> Terms<'_> {
+        Terms {
+            coefficients: &self.coefficients,
+            window: self
+                .initial
+                .iter()
+                .map(|&term| BigUint::from(term))
+                .collect(),
+        }
+    }
+
+    /// Term n counting from 0, each term up to n is computed
+    pub fn term(&self, n: u64) -> BigUint {
+        let mut terms = self.terms();
+        for _ in 0..n {
+            terms.next();
+        }
+        terms.next().unwrap_or_default()
+    }
+
+    pub fn terms_mod(&self, modulus: u64) -> TermsMod<'_> {
+        TermsMod {
+            coefficients: &self.coefficients,
+            modulus,
+            window: self.initial.iter().map(|&term| term % modulus).collect(),
+        }
+    }
+
+    /// Length of the cycle the terms modulo M run in, the Pisano period for Fibonacci,
+    /// `None` when the sequence does not come back to its first terms within the search limit
+    pub fn period_mod(&self, modulus: u64) -> Option<u64> {
+        let mut terms = self.terms_mod(modulus);
+        let start = terms.window.clone();
+        (1..=MAX_PERIOD_SEARCH).find(|_| {
+            terms.next();
+            terms.window == start
+        })
+    }
+
+    /// Term n modulo M in O(k³ log n) from the power of the companion matrix,
+    /// which moves the window of the last k terms one step forward
+    pub fn term_mod(&self, n: u64, modulus: u64) -> u64 {
+        let order = self.coefficients.len();
+        let mut step = vec![vec![0; order]; order];
+        for (row, values) in step.iter_mut().enumerate().take(order - 1) {
+            values[row + 1] = 1 % modulus;
+        }
+        for (column, coefficient) in self.coefficients.iter().rev().enumerate() {
+            step[order - 1][column] = coefficient % modulus;
+        }
+        let power = matrix_power(step, n, modulus);
+        power[0]
+            .iter()
+            .zip(&self.initial)
+            .fold(0, |sum, (&value, &term)| {
+                add_mod(sum, mul_mod(value, term % modulus, modulus), modulus)
+            })
+    }
+}
+
+type Matrix = Vec<Vec<u64>>;
+
+fn add_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 + b as u128) % modulus as u128) as u64
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+fn multiply(a: &Matrix, b: &Matrix, modulus: u64) -> Matrix {
+    let size = a.len();
+    let mut product = vec![vec![0; size]; size];
+    for (row, values) in product.iter_mut().enumerate() {
+        for (column, value) in values.iter_mut().enumerate() {
+            *value = (0..size).fold(0, |sum, index| {
+                add_mod(
+                    sum,
+                    mul_mod(a[row][index], b[index][column], modulus),
+                    modulus,
+                )
+            });
+        }
+    }
+    product
+}
+
+/// Square and multiply over the bits of the exponent
+fn matrix_power(mut base: Matrix, mut exponent: u64, modulus: u64) -> Matrix {
+    let size = base.len();
+    let mut result: Matrix = (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|column| (row == column) as u64 % modulus)
+                .collect()
+        })
+        .collect();
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(&result, &base, modulus);
+        }
+        base = multiply(&base, &base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Terms of any size, the next one is computed from the last k
+pub struct Terms<'a> {
+    coefficients: &'a [u64],
+    window: VecDeque<BigUint>,
+}
+
+impl Iterator for Terms<'_> {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<BigUint> {
+        let mut next = BigUint::zero();
+        for (coefficient, term) in self.coefficients.iter().zip(self.window.iter().rev()) {
+            next = match coefficient {
+                0 => continue,
+                1 => &next + term,
+                _ => &next + &(&BigUint::from(*coefficient) * term),
+            };
+        }
+        self.window.push_back(next);
+        self.window.pop_front()
+    }
+}
+
+pub struct TermsMod<'a> {
+    coefficients: &'a [u64],
+    modulus: u64,
+    window: VecDeque<u64>,
+}
+
+impl Iterator for TermsMod<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let modulus = self.modulus as u128;
+        let next = self
+            .coefficients
+            .iter()
+            .zip(self.window.iter().rev())
+            .fold(0, |sum, (&coefficient, &term)| {
+                (sum + coefficient as u128 * term as u128) % modulus
+            });
+        self.window.push_back(next as u64);
+        self.window.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first(recurrence: &LinearRecurrence, count: usize) -> Vec<String> {
+        recurrence
+            .terms()
+            .take(count)
+            .map(|term| term.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn generates_known_sequences() {
+        assert_eq!(
+            first(&LinearRecurrence::fibonacci(), 8),
+            ["0", "1", "1", "2", "3", "5", "8", "13"]
+        );
+        assert_eq!(
+            first(&LinearRecurrence::lucas(), 6),
+            ["2", "1", "3", "4", "7", "11"]
+        );
+        assert_eq!(
+            first(&LinearRecurrence::tribonacci(), 8),
+            ["0", "0", "1", "1", "2", "4", "7", "13"]
+        );
+        assert_eq!(
+            first(&LinearRecurrence::pell(), 6),
+            ["0", "1", "2", "5", "12", "29"]
+        );
+        assert_eq!(
+            LinearRecurrence::fibonacci().term(100).to_string(),
+            "354224848179261915075"
+        );
+        assert!(LinearRecurrence::new(vec![1, 1], vec![1]).is_err());
+        assert!(LinearRecurrence::new(vec![], vec![]).is_err());
+    }
+
+    #[test]
+    fn computes_terms_modulo_m() {
+        let fibonacci = LinearRecurrence::fibonacci();
+
+        assert_eq!(fibonacci.period_mod(10), Some(60));
+        assert_eq!(fibonacci.period_mod(2), Some(3));
+        assert_eq!(fibonacci.period_mod(1), Some(1));
+        assert_eq!(LinearRecurrence::pell().period_mod(5), Some(12));
+        for n in 0..200 {
+            let term = fibonacci.term(n).to_string();
+            let last_digit = term.chars().last().unwrap().to_digit(10).unwrap() as u64;
+
+            assert_eq!(fibonacci.term_mod(n, 10), last_digit);
+            assert_eq!(fibonacci.terms_mod(10).nth(n as usize), Some(last_digit));
+        }
+        assert_eq!(
+            fibonacci.term_mod(u64::MAX, 10),
+            fibonacci.term_mod(u64::MAX % 60, 10)
+        );
+    }
+
+    #[test]
+    fn finds_terms_modulo_m_without_the_period() {
+        let doubling = LinearRecurrence::new(vec![2], vec![1]).unwrap();
+        let tribonacci = LinearRecurrence::tribonacci();
+
+        assert_eq!(doubling.period_mod(4), None);
+        assert_eq!(doubling.term_mod(1, 4), 2);
+        assert_eq!(doubling.term_mod(u64::MAX, 4), 0);
+        assert_eq!(doubling.term_mod(64, u64::MAX), 1);
+        assert_eq!(
+            LinearRecurrence::fibonacci().term_mod(100, 1_000_000_000_000_000_000),
+            224_848_179_261_915_075
+        );
+        assert_eq!(LinearRecurrence::lucas().term_mod(0, 1), 0);
+        for n in 0..60 {
+            let term = tribonacci.term(n).to_string();
+            let low_digits = term[term.len().saturating_sub(4)..].parse::<u64>().unwrap();
+
+            assert_eq!(tribonacci.term_mod(n, 10_000), low_digits);
+        }
+    }
+}
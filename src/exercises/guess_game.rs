@@ -1,30 +1,325 @@
+mod high_scores;
+mod reverse;
+
 use io::stdin;
-use rand::RngExt;
+use rand::rngs::StdRng;
+use rand::{Rng, RngExt, SeedableRng};
 use std::cmp::Ordering;
 use std::io;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
 
-pub fn run() {
-    println!("Guess the number!");
-    let number = rand::rng()
-        .random_range(1..=10);
-    loop {
-        println!("Please input number: ");
+use clap::{Parser, Subcommand};
+
+use high_scores::{Entry, HIGH_SCORES_FILE_NAME, HighScores};
+use reverse::reverse;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    fn settings(&self) -> Settings {
+        let (max, attempts) = match self {
+            Difficulty::Easy => (10, 5),
+            Difficulty::Medium => (100, 7),
+            Difficulty::Hard => (1000, 10),
+        };
+        Settings {
+            min: 1,
+            max,
+            attempts,
+        }
+    }
+}
+
+/// Secret number range, both ends included, and the number of guesses allowed
+#[derive(Debug, PartialEq)]
+struct Settings {
+    min: u32,
+    max: u32,
+    attempts: u32,
+}
+
+impl Settings {
+    /// Points for a win: 100 for every bit of information in the range, scaled by
+    /// the share of attempts that were left when the number was found
+    fn score(&self, used: u32) -> u32 {
+        let bits = (u32::BITS - (self.max - self.min).leading_zeros()).max(1) as u64;
+        let left = (self.attempts - used + 1) as u64;
+        (bits * 100 * left / self.attempts as u64) as u32
+    }
+}
+
+#[derive(Parser)]
+#[command(
+    name = "guess_game",
+    about = "Guess the number",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Range and attempts preset, easy 1-10 in 5, medium 1-100 in 7, hard 1-1000 in 10
+    #[arg(short, long, value_enum, default_value_t = Difficulty::Medium)]
+    difficulty: Difficulty,
+    /// Smallest possible number instead of the preset one
+    #[arg(long)]
+    min: Option<u32>,
+    /// Largest possible number instead of the preset one
+    #[arg(long)]
+    max: Option<u32>,
+    /// Number of guesses instead of the preset one
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    attempts: Option<u32>,
+    /// Seed for the secret number, the same seed gives the same number,
+    /// so seeded games do not enter the high score table
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Name written to the high score table
+    #[arg(short, long)]
+    name: Option<String>,
+    /// High score table file
+    #[arg(long, global = true, default_value = HIGH_SCORES_FILE_NAME)]
+    scores: PathBuf,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the high score table
+    Scores,
+    /// Think of a number and let the computer guess it
+    Reverse {
+        /// Smallest possible number
+        #[arg(long, default_value_t = 1)]
+        min: u32,
+        /// Largest possible number
+        #[arg(long, default_value_t = 100)]
+        max: u32,
+    },
+}
+
+fn settings(cli: &Cli) -> Result<Settings, String> {
+    let preset = cli.difficulty.settings();
+    let settings = Settings {
+        min: cli.min.unwrap_or(preset.min),
+        max: cli.max.unwrap_or(preset.max),
+        attempts: cli.attempts.unwrap_or(preset.attempts),
+    };
+    if settings.min >= settings.max {
+        return Err(format!(
+            "the range {}-{} needs at least two numbers",
+            settings.min, settings.max
+        ));
+    }
+    Ok(settings)
+}
+
+/// Plays one game, returns the number of guesses used when the number was found
+fn play(
+    settings: &Settings,
+    rng: &mut impl Rng,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Option<u32>, String> {
+    let _ = writeln!(output, "Guess the number!");
+    let _ = writeln!(
+        output,
+        "It is between {} and {}, you have {} attempts",
+        settings.min, settings.max, settings.attempts
+    );
+    let number = rng.random_range(settings.min..=settings.max);
+    let mut used = 0;
+    while used < settings.attempts {
+        let _ = writeln!(output, "Please input number: ");
         let mut provided_number = String::new();
-        stdin()
-            .read_line(&mut provided_number)
-            .expect("Read line failed");
+        match input.read_line(&mut provided_number) {
+            Ok(0) => return Ok(None),
+            Ok(_) => {}
+            Err(error) => return Err(format!("Read line failed: {error}")),
+        }
 
-        let Ok(guess) = provided_number.trim().parse::<usize>() else {
+        let Ok(guess) = provided_number.trim().parse::<u32>() else {
+            let _ = writeln!(output, "That is not a number");
             continue;
         };
+        if !(settings.min..=settings.max).contains(&guess) {
+            let _ = writeln!(
+                output,
+                "The number is between {} and {}",
+                settings.min, settings.max
+            );
+            continue;
+        }
+        used += 1;
 
         match guess.cmp(&number) {
-            Ordering::Less => println!("Too small"),
-            Ordering::Greater => println!("Too big"),
+            Ordering::Less => print_hint(output, "Too small", settings.attempts - used),
+            Ordering::Greater => print_hint(output, "Too big", settings.attempts - used),
             Ordering::Equal => {
-                println!("You won!");
-                break;
+                let _ = writeln!(output, "You won!");
+                return Ok(Some(used));
+            }
+        }
+    }
+    let _ = writeln!(output, "You lost, the number was {number}");
+    Ok(None)
+}
+
+fn print_hint(output: &mut impl Write, hint: &str, left: u32) {
+    if left > 0 {
+        let _ = writeln!(output, "{hint}, attempts left: {left}");
+    } else {
+        let _ = writeln!(output, "{hint}");
+    }
+}
+
+fn player_name(cli: &Cli) -> String {
+    cli.name
+        .clone()
+        .or_else(|| std::env::var("USER").ok())
+        .unwrap_or_else(|| String::from("player"))
+}
+
+fn guess_game(cli: Cli) -> Result<(), String> {
+    match cli.command {
+        Some(Command::Scores) => {
+            print!("{}", HighScores::load(&cli.scores)?);
+            return Ok(());
+        }
+        Some(Command::Reverse { min, max }) => {
+            if min > max {
+                return Err(format!("the range {min}-{max} is empty"));
             }
+            reverse(min, max, &mut stdin().lock(), &mut io::stdout())?;
+            return Ok(());
         }
+        None => {}
+    }
+
+    let settings = settings(&cli)?;
+    let mut input = stdin().lock();
+    let mut output = io::stdout();
+    let used = match cli.seed {
+        Some(seed) => play(
+            &settings,
+            &mut StdRng::seed_from_u64(seed),
+            &mut input,
+            &mut output,
+        )?,
+        None => play(&settings, &mut rand::rng(), &mut input, &mut output)?,
+    };
+    let Some(used) = used else {
+        return Ok(());
+    };
+
+    let score = settings.score(used);
+    println!("Score: {score}");
+    if cli.seed.is_some() {
+        println!("Seeded games are not recorded in the high score table");
+        return Ok(());
+    }
+    let mut high_scores = HighScores::load(&cli.scores)?;
+    let place = high_scores.add(Entry {
+        name: player_name(&cli),
+        score,
+        min: settings.min,
+        max: settings.max,
+        attempts: used,
+    });
+    if let Some(place) = place {
+        println!("New high score, place {place}");
+        high_scores.save(&cli.scores)?;
+    }
+    print!("{high_scores}");
+    Ok(())
+}
+
+pub fn run() {
+    if let Err(error) = guess_game(Cli::parse()) {
+        println!("Error: {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn secret(settings: &Settings, seed: u64) -> u32 {
+        StdRng::seed_from_u64(seed).random_range(settings.min..=settings.max)
+    }
+
+    fn play_input(settings: &Settings, seed: u64, input: &str) -> (Option<u32>, String) {
+        let mut output = Vec::new();
+        let used = play(
+            settings,
+            &mut StdRng::seed_from_u64(seed),
+            &mut Cursor::new(input),
+            &mut output,
+        )
+        .unwrap();
+        (used, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn counts_only_valid_guesses_in_range() {
+        let settings = Difficulty::Medium.settings();
+        let number = secret(&settings, 47);
+        let wrong = if number == 1 { 2 } else { 1 };
+
+        let (used, output) =
+            play_input(&settings, 47, &format!("abc\n0\n101\n{wrong}\n{number}\n"));
+
+        assert_eq!(used, Some(2));
+        assert!(output.contains("That is not a number"));
+        assert!(output.contains("The number is between 1 and 100"));
+        assert!(output.contains("attempts left: 6"));
+        assert!(output.ends_with("You won!\n"));
+    }
+
+    #[test]
+    fn ends_after_the_last_attempt() {
+        let settings = Settings {
+            min: 1,
+            max: 10,
+            attempts: 2,
+        };
+        let number = secret(&settings, 3);
+        let wrong = if number == 1 { 2 } else { 1 };
+
+        let (used, output) = play_input(&settings, 3, &format!("{wrong}\n{wrong}\n{number}\n"));
+
+        assert_eq!(used, None);
+        assert!(output.ends_with(&format!("You lost, the number was {number}\n")));
+    }
+
+    #[test]
+    fn scores_by_range_and_attempts_left() {
+        let medium = Difficulty::Medium.settings();
+
+        assert_eq!(medium.score(1), 700);
+        assert_eq!(medium.score(7), 100);
+        assert_eq!(Difficulty::Hard.settings().score(1), 1000);
+        assert_eq!(Difficulty::Easy.settings().score(3), 240);
+        assert!(settings(&Cli::parse_from(["guess_game", "--min", "5", "--max", "5"])).is_err());
+        assert_eq!(
+            settings(&Cli::parse_from([
+                "guess_game",
+                "-d",
+                "easy",
+                "--max",
+                "20"
+            ])),
+            Ok(Settings {
+                min: 1,
+                max: 20,
+                attempts: 5
+            })
+        );
     }
 }
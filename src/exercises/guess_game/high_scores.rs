@@ -0,0 +1,172 @@
+use std::fmt::Display;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use csv::ReaderBuilder;
+
+use crate::exercises::utils::csv_string;
+
+pub const HIGH_SCORES_FILE_NAME: &str = "guess_scores.csv";
+const MAX_ENTRIES: usize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub name: String,
+    pub score: u32,
+    pub min: u32,
+    pub max: u32,
+    pub attempts: u32,
+}
+
+/// Best scores first, an earlier entry stays ahead of a later one with the same score
+#[derive(Debug, Default, PartialEq)]
+pub struct HighScores {
+    entries: Vec<Entry>,
+}
+
+impl HighScores {
+    /// Reads `name,score,min,max,attempts` rows, a missing file is an empty table
+    pub fn load(path: &Path) -> Result<HighScores, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(HighScores::default()),
+            Err(error) => return Err(format!("Cannot read {}: {error}", path.display())),
+        };
+        let mut reader = ReaderBuilder::new().from_reader(content.as_bytes());
+        let mut high_scores = HighScores::default();
+        for record in reader.records() {
+            let record = record.map_err(|error| format!("{}: {error}", path.display()))?;
+            let line = record.position().map_or(0, |position| position.line());
+            let invalid = || format!("{}: invalid row on line {line}", path.display());
+            let [name, score, min, max, attempts] = [0, 1, 2, 3, 4].map(|index| record.get(index));
+            let number = |field: Option<&str>| field.and_then(|field| field.trim().parse().ok());
+            high_scores.add(Entry {
+                name: name.ok_or_else(invalid)?.trim().to_string(),
+                score: number(score).ok_or_else(invalid)?,
+                min: number(min).ok_or_else(invalid)?,
+                max: number(max).ok_or_else(invalid)?,
+                attempts: number(attempts).ok_or_else(invalid)?,
+            });
+        }
+        Ok(high_scores)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut records = vec![
+            ["name", "score", "min", "max", "attempts"]
+                .map(String::from)
+                .to_vec(),
+        ];
+        for entry in &self.entries {
+            records.push(vec![
+                entry.name.clone(),
+                entry.score.to_string(),
+                entry.min.to_string(),
+                entry.max.to_string(),
+                entry.attempts.to_string(),
+            ]);
+        }
+        fs::write(path, csv_string(&records)?)
+            .map_err(|error| format!("Cannot write {}: {error}", path.display()))
+    }
+
+    /// Place of the entry counted from 1, `None` when it did not make the table
+    pub fn add(&mut self, entry: Entry) -> Option<usize> {
+        let index = self
+            .entries
+            .iter()
+            .position(|other| other.score < entry.score)
+            .unwrap_or(self.entries.len());
+        if index >= MAX_ENTRIES {
+            return None;
+        }
+        self.entries.insert(index, entry);
+        self.entries.truncate(MAX_ENTRIES);
+        Some(index + 1)
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
+
+impl Display for HighScores {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.entries.is_empty() {
+            return writeln!(f, "No high scores yet");
+        }
+        writeln!(
+            f,
+            " #  {:<16} {:>6}  {:<12} {:>8}",
+            "Name", "Score", "Range", "Attempts"
+        )?;
+        for (index, entry) in self.entries.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>2}  {:<16} {:>6}  {:<12} {:>8}",
+                index + 1,
+                entry.name,
+                entry.score,
+                format!("{}-{}", entry.min, entry.max),
+                entry.attempts
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: u32) -> Entry {
+        Entry {
+            name: name.to_string(),
+            score,
+            min: 1,
+            max: 100,
+            attempts: 4,
+        }
+    }
+
+    #[test]
+    fn keeps_the_best_scores_in_order() {
+        let mut high_scores = HighScores::default();
+        for (index, score) in [300, 500, 300, 100, 200, 400, 600, 150, 250, 350]
+            .into_iter()
+            .enumerate()
+        {
+            high_scores.add(entry(&format!("P{index}"), score));
+        }
+
+        assert_eq!(high_scores.add(entry("late", 300)), Some(7));
+        assert_eq!(high_scores.add(entry("low", 100)), None);
+        assert_eq!(high_scores.entries().len(), 10);
+        assert_eq!(
+            high_scores
+                .entries()
+                .iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["P6", "P1", "P5", "P9", "P0", "P2", "late", "P8", "P4", "P7"]
+        );
+    }
+
+    #[test]
+    fn saves_and_loads_the_table() {
+        let path = std::env::temp_dir().join(format!("guess-scores-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut high_scores = HighScores::load(&path).unwrap();
+        high_scores.add(entry("Ala, Ola", 250));
+        high_scores.add(entry("Bob", 400));
+
+        high_scores.save(&path).unwrap();
+        let loaded = HighScores::load(&path).unwrap();
+        fs::write(&path, "name,score,min,max,attempts\nBob,many,1,100,4\n").unwrap();
+
+        assert_eq!(loaded, high_scores);
+        assert!(HighScores::load(&path).is_err());
+        fs::remove_file(path).unwrap();
+    }
+}
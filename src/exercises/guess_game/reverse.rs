@@ -0,0 +1,201 @@
+use std::io::{BufRead, Write};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Answer {
+    Higher,
+    Lower,
+    Correct,
+}
+
+pub fn parse_answer(line: &str) -> Option<Answer> {
+    match line.trim().to_ascii_lowercase().as_str() {
+        "higher" | "h" | "+" | ">" => Some(Answer::Higher),
+        "lower" | "l" | "-" | "<" => Some(Answer::Lower),
+        "correct" | "c" | "=" | "yes" | "y" => Some(Answer::Correct),
+        _ => None,
+    }
+}
+
+/// Guesses that split the numbers still possible in half, each answer removes the wrong half
+pub struct Guesser {
+    min: u32,
+    max: u32,
+    // Kept wider than u32 so the bounds can cross when the answers contradict each other
+    low: i64,
+    high: i64,
+    too_small: Option<u32>,
+    too_big: Option<u32>,
+    guesses: u32,
+}
+
+impl Guesser {
+    pub fn new(min: u32, max: u32) -> Guesser {
+        Guesser {
+            min,
+            max,
+            low: min as i64,
+            high: max as i64,
+            too_small: None,
+            too_big: None,
+            guesses: 0,
+        }
+    }
+
+    pub fn next_guess(&self) -> u32 {
+        (self.low + (self.high - self.low) / 2) as u32
+    }
+
+    /// Number of guesses when the answer is correct, an error when no number fits all answers
+    pub fn answer(&mut self, guess: u32, answer: Answer) -> Result<Option<u32>, String> {
+        self.guesses += 1;
+        match answer {
+            Answer::Correct => return Ok(Some(self.guesses)),
+            Answer::Higher => {
+                self.low = guess as i64 + 1;
+                self.too_small = Some(guess);
+            }
+            Answer::Lower => {
+                self.high = guess as i64 - 1;
+                self.too_big = Some(guess);
+            }
+        }
+        if self.low <= self.high {
+            return Ok(None);
+        }
+        Err(match (self.too_small, self.too_big) {
+            (Some(small), Some(big)) => {
+                format!("{small} is too small and {big} is too big, no number is left")
+            }
+            (Some(small), None) => {
+                format!("{small} is too small but the numbers end at {}", self.max)
+            }
+            (None, _) => format!("{} is too big but the numbers start at {}", guess, self.min),
+        })
+    }
+}
+
+/// Guesses binary search needs in the worst case for `count` numbers, `⌊log2 count⌋ + 1`
+pub fn optimum(count: u64) -> u32 {
+    u64::BITS - count.leading_zeros()
+}
+
+/// Computer guesses the number the player thinks of, returns the guesses used when found
+pub fn reverse(
+    min: u32,
+    max: u32,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Option<u32>, String> {
+    let _ = writeln!(
+        output,
+        "Think of a number between {min} and {max}, answer higher, lower or correct"
+    );
+    let mut guesser = Guesser::new(min, max);
+    loop {
+        let guess = guesser.next_guess();
+        let _ = writeln!(output, "Is it {guess}?");
+        let mut line = String::new();
+        match input.read_line(&mut line) {
+            Ok(0) => return Ok(None),
+            Ok(_) => {}
+            Err(error) => return Err(format!("Read line failed: {error}")),
+        }
+        let Some(answer) = parse_answer(&line) else {
+            let _ = writeln!(output, "Answer higher, lower or correct");
+            continue;
+        };
+
+        match guesser.answer(guess, answer) {
+            Ok(None) => {}
+            Ok(Some(guesses)) => {
+                let count = max as u64 - min as u64 + 1;
+                let optimum = optimum(count);
+                let _ = writeln!(output, "Found {guess} in {guesses} guesses");
+                let _ = writeln!(
+                    output,
+                    "Binary search needs at most {optimum} for {count} numbers (log2 {count} = {:.2})",
+                    (count as f64).log2()
+                );
+                return Ok(Some(guesses));
+            }
+            Err(cheat) => {
+                let _ = writeln!(output, "Cheater! {cheat}");
+                return Ok(None);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use std::io::Cursor;
+
+    use super::*;
+
+    // Plays honestly for the number and returns the guesses needed
+    fn find(min: u32, max: u32, number: u32) -> u32 {
+        let mut guesser = Guesser::new(min, max);
+        loop {
+            let guess = guesser.next_guess();
+            let answer = match number.cmp(&guess) {
+                Ordering::Less => Answer::Lower,
+                Ordering::Greater => Answer::Higher,
+                Ordering::Equal => Answer::Correct,
+            };
+            if let Some(guesses) = guesser.answer(guess, answer).unwrap() {
+                return guesses;
+            }
+        }
+    }
+
+    #[test]
+    fn finds_every_number_within_the_optimum() {
+        assert_eq!(optimum(100), 7);
+        assert_eq!(optimum(1), 1);
+        assert_eq!(optimum(1 << 32), 33);
+        assert_eq!((1..=100).map(|number| find(1, 100, number)).max(), Some(7));
+        assert!(find(0, u32::MAX, u32::MAX) <= 33);
+        assert!(find(0, u32::MAX, 0) <= 33);
+        assert_eq!(find(5, 5, 5), 1);
+    }
+
+    #[test]
+    fn detects_contradicting_answers() {
+        let mut guesser = Guesser::new(1, 100);
+        assert_eq!(guesser.answer(50, Answer::Higher), Ok(None));
+        assert_eq!(guesser.answer(75, Answer::Lower), Ok(None));
+        assert_eq!(guesser.answer(62, Answer::Lower), Ok(None));
+        assert_eq!(guesser.answer(56, Answer::Lower), Ok(None));
+        assert_eq!(guesser.answer(53, Answer::Lower), Ok(None));
+        assert_eq!(guesser.answer(51, Answer::Higher), Ok(None));
+        assert_eq!(
+            guesser.answer(52, Answer::Lower),
+            Err(String::from(
+                "51 is too small and 52 is too big, no number is left"
+            ))
+        );
+        assert!(Guesser::new(1, 10).answer(10, Answer::Higher).is_err());
+        assert!(Guesser::new(0, 10).answer(0, Answer::Lower).is_err());
+    }
+
+    #[test]
+    fn guesses_from_the_given_answers() {
+        let mut output = Vec::new();
+
+        let guesses = reverse(
+            1,
+            100,
+            &mut Cursor::new("h\nmaybe\nlower\nc\n"),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(guesses, Some(3));
+        assert!(output.contains(
+            "Is it 50?\nIs it 75?\nAnswer higher, lower or correct\nIs it 75?\nIs it 62?\n"
+        ));
+        assert!(output.contains("Found 62 in 3 guesses\nBinary search needs at most 7 for 100 numbers (log2 100 = 6.64)\n"));
+    }
+}
@@ -1,56 +1,501 @@
-const EUR: &str = "EUR";
-const PLN: &str = "PLN";
+mod allocation;
+mod bag;
+mod currency;
+mod format;
+mod rates;
+mod rounding;
 
-#[derive(Debug)]
-struct MonetaryAmount {
-    value: f64,
-    currency: String,
+use std::ops::{Add, Mul, Neg, Sub};
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDate};
+use clap::{Parser, Subcommand};
+use thiserror::Error;
+
+pub use bag::MoneyBag;
+pub use currency::Currency;
+pub use format::{Locale, MoneyFormatter, NegativeStyle};
+pub use rates::{ExchangeRates, Rate};
+pub use rounding::RoundingMode;
+
+const RATES_FILE_NAME: &str = "rates.csv";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum MoneyError {
+    #[error("currency mismatch: expected {expected}, found {found}")]
+    CurrencyMismatch { expected: Currency, found: Currency },
+    #[error("arithmetic overflow")]
+    Overflow,
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("unknown currency: {0}")]
+    UnknownCurrency(String),
+    #[error("invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("unknown locale: {0}")]
+    UnknownLocale(String),
+    #[error("invalid allocation: {0}")]
+    InvalidAllocation(String),
+    #[error("invalid exchange rate: {0}")]
+    InvalidRate(String),
+    #[error("invalid rate file: {0}")]
+    InvalidRateFile(String),
+    #[error("no exchange rate from {from} to {to} on {date}")]
+    MissingRate {
+        from: Currency,
+        to: Currency,
+        date: NaiveDate,
+    },
 }
 
-impl MonetaryAmount {
-    fn add(&mut self, other: &MonetaryAmount) -> Result<(), String> {
-        self.check_currency(other)?;
-        self.value += other.value;
-        Ok(())
+/// Amount of money kept as an integer number of minor units (e.g. cents)
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Money {
+    minor: i64,
+    currency: Currency,
+}
+
+fn power_of_ten(exponent: u32) -> Result<i128, MoneyError> {
+    10_i128.checked_pow(exponent).ok_or(MoneyError::Overflow)
+}
+
+fn to_minor(value: i128) -> Result<i64, MoneyError> {
+    i64::try_from(value).map_err(|_| MoneyError::Overflow)
+}
+
+impl Money {
+    pub fn from_minor(minor: i64, currency: Currency) -> Self {
+        Money { minor, currency }
     }
 
-    fn subtract(&mut self, other: &MonetaryAmount) -> Result<(), String> {
-        self.check_currency(other)?;
-        self.value -= other.value;
-        Ok(())
+    pub fn from_major(major: i64, currency: Currency) -> Result<Self, MoneyError> {
+        let factor = power_of_ten(currency.minor_units())?;
+        Ok(Money::from_minor(
+            to_minor(major as i128 * factor)?,
+            currency,
+        ))
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Money::from_minor(0, currency)
+    }
+
+    /// Parses a plain decimal such as "-12.345", extra fraction digits are rounded
+    pub fn from_decimal(
+        value: &str,
+        currency: Currency,
+        rounding: RoundingMode,
+    ) -> Result<Self, MoneyError> {
+        let invalid = || MoneyError::InvalidAmount(value.to_string());
+        let trimmed = value.trim();
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer.is_empty() && fraction.is_empty()
+            || !integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let scale = fraction.len() as u32;
+        let mut unscaled: i128 = format!("{integer}{fraction}")
+            .parse()
+            .map_err(|_| MoneyError::Overflow)?;
+        if negative {
+            unscaled = -unscaled;
+        }
+        let minor_units = currency.minor_units();
+        let minor = if scale <= minor_units {
+            unscaled
+                .checked_mul(power_of_ten(minor_units - scale)?)
+                .ok_or(MoneyError::Overflow)?
+        } else {
+            rounding.divide(unscaled, power_of_ten(scale - minor_units)?)
+        };
+        Ok(Money::from_minor(to_minor(minor)?, currency))
+    }
+
+    pub fn minor(&self) -> i64 {
+        self.minor
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.minor == 0
     }
 
-    fn check_currency(&self, other: &MonetaryAmount) -> Result<(), String> {
+    pub fn is_negative(&self) -> bool {
+        self.minor < 0
+    }
+
+    fn check_currency(&self, other: &Money) -> Result<(), MoneyError> {
         if self.currency != other.currency {
-            return Err(String::from("Currency not match!"));
+            return Err(MoneyError::CurrencyMismatch {
+                expected: self.currency,
+                found: other.currency,
+            });
         }
         Ok(())
     }
 
-    fn convert(amount: &MonetaryAmount, exchange_rate: f64, currency: &str) -> Self {
-        Self {
-            value: amount.value / exchange_rate,
-            currency: String::from(currency),
+    pub fn checked_add(self, other: Money) -> Result<Money, MoneyError> {
+        self.check_currency(&other)?;
+        let minor = self
+            .minor
+            .checked_add(other.minor)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Money::from_minor(minor, self.currency))
+    }
+
+    pub fn checked_sub(self, other: Money) -> Result<Money, MoneyError> {
+        self.check_currency(&other)?;
+        let minor = self
+            .minor
+            .checked_sub(other.minor)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Money::from_minor(minor, self.currency))
+    }
+
+    pub fn checked_neg(self) -> Result<Money, MoneyError> {
+        let minor = self.minor.checked_neg().ok_or(MoneyError::Overflow)?;
+        Ok(Money::from_minor(minor, self.currency))
+    }
+
+    pub fn checked_mul(self, factor: i64) -> Result<Money, MoneyError> {
+        let minor = self.minor.checked_mul(factor).ok_or(MoneyError::Overflow)?;
+        Ok(Money::from_minor(minor, self.currency))
+    }
+
+    /// Multiplies by `numerator / denominator`, e.g. (23, 100) for 23% VAT
+    pub fn mul_ratio(
+        self,
+        numerator: i64,
+        denominator: i64,
+        rounding: RoundingMode,
+    ) -> Result<Money, MoneyError> {
+        if denominator == 0 {
+            return Err(MoneyError::DivisionByZero);
         }
+        let product = self.minor as i128 * numerator as i128;
+        let minor = rounding.divide(product, denominator as i128);
+        Ok(Money::from_minor(to_minor(minor)?, self.currency))
+    }
+
+    /// Converts with a known rate, the result is rounded to the minor unit of the target currency
+    pub fn convert_at(
+        self,
+        rate: Rate,
+        to: Currency,
+        rounding: RoundingMode,
+    ) -> Result<Money, MoneyError> {
+        let numerator = (self.minor as i128)
+            .checked_mul(rate.numerator())
+            .and_then(|value| value.checked_mul(power_of_ten(to.minor_units()).ok()?))
+            .ok_or(MoneyError::Overflow)?;
+        let denominator = rate
+            .denominator()
+            .checked_mul(power_of_ten(self.currency.minor_units())?)
+            .ok_or(MoneyError::Overflow)?;
+        let minor = rounding.divide(numerator, denominator);
+        Ok(Money::from_minor(to_minor(minor)?, to))
     }
 
-    fn new(value: f64, currency: &str) -> Self {
-        Self {
-            value,
-            currency: String::from(currency),
+    pub fn convert(
+        self,
+        rates: &ExchangeRates,
+        to: Currency,
+        date: NaiveDate,
+        rounding: RoundingMode,
+    ) -> Result<Money, MoneyError> {
+        let rate = rates.rate(self.currency, to, date)?;
+        self.convert_at(rate, to, rounding)
+    }
+
+    /// Plain decimal representation with all minor digits, e.g. "-1234.50"
+    pub fn to_decimal_string(self) -> String {
+        let minor_units = self.currency.minor_units() as usize;
+        let sign = if self.minor < 0 { "-" } else { "" };
+        let digits = format!(
+            "{:0>width$}",
+            self.minor.unsigned_abs(),
+            width = minor_units + 1
+        );
+        let (integer, fraction) = digits.split_at(digits.len() - minor_units);
+        if fraction.is_empty() {
+            format!("{sign}{integer}")
+        } else {
+            format!("{sign}{integer}.{fraction}")
         }
     }
 }
 
+impl Add for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn add(self, rhs: Money) -> Self::Output {
+        self.checked_add(rhs)
+    }
+}
+
+impl Sub for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn sub(self, rhs: Money) -> Self::Output {
+        self.checked_sub(rhs)
+    }
+}
+
+impl Neg for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg()
+    }
+}
+
+impl Mul<i64> for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        self.checked_mul(rhs)
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "money", about = "Money calculations")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert an amount, e.g. convert 100 EUR PLN --date 2026-05-01
+    Convert {
+        amount: String,
+        from: String,
+        to: String,
+        /// Date of the exchange rate, today when omitted
+        #[arg(short, long)]
+        date: Option<NaiveDate>,
+        /// Rate file (.csv or .json) with date, from, to and rate fields
+        #[arg(short, long, default_value = RATES_FILE_NAME)]
+        rates: PathBuf,
+        #[arg(long, value_enum, default_value_t = RoundingMode::HalfEven)]
+        rounding: RoundingMode,
+        /// Format amounts for a locale: pl-PL, en-US or de-DE
+        #[arg(short, long)]
+        locale: Option<String>,
+    },
+}
+
+fn convert(
+    amount: &str,
+    from: &str,
+    to: &str,
+    date: NaiveDate,
+    rates: &Path,
+    rounding: RoundingMode,
+) -> Result<(Money, Money, Rate), MoneyError> {
+    let from = Currency::from_code(from)?;
+    let to = Currency::from_code(to)?;
+    let amount = Money::from_decimal(amount, from, rounding)?;
+    let rates = ExchangeRates::load(rates)?;
+    let rate = rates.rate(from, to, date)?;
+    Ok((amount, amount.convert_at(rate, to, rounding)?, rate))
+}
+
 pub fn run() {
-    let mut balance = MonetaryAmount::new(1_000.0, EUR);
-    let income = MonetaryAmount::new(2_000.0, EUR);
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Command::Convert {
+            amount,
+            from,
+            to,
+            date,
+            rates,
+            rounding,
+            locale,
+        }) => {
+            let date = date.unwrap_or_else(|| Local::now().date_naive());
+            let formatter = match locale.as_deref().map(Locale::try_from).transpose() {
+                Ok(locale) => locale.map(MoneyFormatter::new),
+                Err(error) => return println!("Error: {error}"),
+            };
+            let format = |money: Money| match formatter {
+                Some(formatter) => formatter.format(money),
+                None => money.to_string(),
+            };
+            match convert(&amount, &from, &to, date, &rates, rounding) {
+                Ok((amount, converted, rate)) => println!(
+                    "{} = {} (rate {rate} on {date})",
+                    format(amount),
+                    format(converted)
+                ),
+                Err(error) => println!("Error: {error}"),
+            }
+        }
+        None => demo(),
+    }
+}
+
+fn demo() {
+    let balance = Money::from_major(1_000, Currency::EUR).expect("Valid amount");
+    let income = Money::from_decimal("2000.50", Currency::EUR, RoundingMode::HalfEven)
+        .expect("Valid amount");
+
+    match balance + income {
+        Ok(total) => println!("Balance updated: {}", total.to_decimal_string()),
+        Err(error) => println!("Error: {error}"),
+    }
+
+    let fee = Money::from_major(5, Currency::PLN).expect("Valid amount");
+    match balance - fee {
+        Ok(total) => println!("Balance updated: {}", total.to_decimal_string()),
+        Err(error) => println!("Error: {error}"),
+    }
+
+    let price =
+        Money::from_decimal("19.99", Currency::PLN, RoundingMode::HalfEven).expect("Valid amount");
+    for rounding in [
+        RoundingMode::HalfEven,
+        RoundingMode::HalfUp,
+        RoundingMode::Floor,
+        RoundingMode::Ceil,
+    ] {
+        match price.mul_ratio(23, 100, rounding) {
+            Ok(vat) => println!("VAT ({rounding:?}): {}", vat.to_decimal_string()),
+            Err(error) => println!("Error: {error}"),
+        }
+    }
+
+    let debt = Money::from_minor(-123_456, Currency::PLN);
+    for locale in [Locale::PlPl, Locale::EnUs, Locale::DeDe] {
+        let formatter = MoneyFormatter::new(locale).with_negative_style(NegativeStyle::Accounting);
+        println!("{debt} in {locale}: {}", formatter.format(debt));
+    }
+
+    let wallet: MoneyBag = [balance, income, fee, price].into_iter().sum();
+    println!("Wallet: {wallet}");
+
+    let bill = Money::from_major(100, Currency::PLN).expect("Valid amount");
+    match bill.split(3) {
+        Ok(parts) => {
+            let parts: Vec<String> = parts.iter().map(|part| part.to_decimal_string()).collect();
+            println!("Bill split: {}", parts.join(" + "));
+        }
+        Err(error) => println!("Error: {error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minor_units_follow_currency_table() {
+        assert_eq!(Money::from_major(3, Currency::EUR).unwrap().minor(), 300);
+        assert_eq!(Money::from_major(3, Currency::JPY).unwrap().minor(), 3);
+        assert_eq!(Money::from_major(3, Currency::KWD).unwrap().minor(), 3000);
+        assert_eq!(Currency::from_code("pln"), Ok(Currency::PLN));
+        assert_eq!(Currency::from_numeric(978), Ok(Currency::EUR));
+    }
+
+    #[test]
+    fn operators_reject_currency_mismatch() {
+        let euro = Money::from_major(1, Currency::EUR).unwrap();
+        let zloty = Money::from_major(1, Currency::PLN).unwrap();
 
-    match balance.add(&income) {
-        Ok(_) => println!("Balance updated: {:?}", balance),
-        Err(message) => println!("Error: {message}"),
+        assert_eq!(
+            euro + zloty,
+            Err(MoneyError::CurrencyMismatch {
+                expected: Currency::EUR,
+                found: Currency::PLN
+            })
+        );
+        assert_eq!(euro - euro, Ok(Money::zero(Currency::EUR)));
+        assert_eq!(-euro, Ok(Money::from_minor(-100, Currency::EUR)));
+        assert_eq!(euro * 3, Ok(Money::from_minor(300, Currency::EUR)));
     }
 
-    let result_pln = MonetaryAmount::convert(&balance, 0.2, PLN);
-    println!("Balance converted: {:?}", result_pln);
+    #[test]
+    fn arithmetic_overflow_is_reported() {
+        let max = Money::from_minor(i64::MAX, Currency::USD);
+        let min = Money::from_minor(i64::MIN, Currency::USD);
+
+        assert_eq!(
+            max + Money::from_minor(1, Currency::USD),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(-min, Err(MoneyError::Overflow));
+        assert_eq!(max * 2, Err(MoneyError::Overflow));
+        assert_eq!(
+            Money::from_major(i64::MAX, Currency::USD),
+            Err(MoneyError::Overflow)
+        );
+    }
+
+    #[test]
+    fn rounding_modes_handle_ties_and_negatives() {
+        let cases = [
+            ("2.345", RoundingMode::HalfEven, 234),
+            ("2.355", RoundingMode::HalfEven, 236),
+            ("2.345", RoundingMode::HalfUp, 235),
+            ("-2.345", RoundingMode::HalfUp, -235),
+            ("-2.345", RoundingMode::HalfEven, -234),
+            ("2.341", RoundingMode::Ceil, 235),
+            ("-2.349", RoundingMode::Ceil, -234),
+            ("2.349", RoundingMode::Floor, 234),
+            ("-2.341", RoundingMode::Floor, -235),
+        ];
+        for (value, rounding, expected) in cases {
+            let money = Money::from_decimal(value, Currency::EUR, rounding).unwrap();
+            assert_eq!(money.minor(), expected, "{value} {rounding:?}");
+        }
+    }
+
+    #[test]
+    fn decimal_string_round_trips() {
+        for value in ["0.05", "-0.05", "1234.50", "-99999.99"] {
+            let money = Money::from_decimal(value, Currency::PLN, RoundingMode::HalfEven).unwrap();
+            assert_eq!(money.to_decimal_string(), value);
+        }
+        assert_eq!(
+            Money::from_minor(-7, Currency::JPY).to_decimal_string(),
+            "-7"
+        );
+        assert!(Money::from_decimal("1.2.3", Currency::PLN, RoundingMode::HalfEven).is_err());
+        assert!(Money::from_decimal("", Currency::PLN, RoundingMode::HalfEven).is_err());
+    }
+
+    #[test]
+    fn mul_ratio_rounds_the_result() {
+        let price = Money::from_minor(1999, Currency::PLN);
+
+        assert_eq!(
+            price
+                .mul_ratio(23, 100, RoundingMode::HalfUp)
+                .unwrap()
+                .minor(),
+            460
+        );
+        assert_eq!(
+            price
+                .mul_ratio(23, 100, RoundingMode::Floor)
+                .unwrap()
+                .minor(),
+            459
+        );
+        assert_eq!(
+            price.mul_ratio(1, 0, RoundingMode::Floor),
+            Err(MoneyError::DivisionByZero)
+        );
+    }
 }
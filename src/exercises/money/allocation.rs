@@ -0,0 +1,136 @@
+use super::{Money, MoneyError, to_minor};
+
+impl Money {
+    /// Splits the amount proportionally to the ratios, the parts always sum to the original.
+    /// Leftover minor units go one each to the parts with the largest fractional share,
+    /// ties are resolved in favour of the earlier part.
+    pub fn allocate(self, ratios: &[u32]) -> Result<Vec<Money>, MoneyError> {
+        let total_ratio: u128 = ratios.iter().map(|ratio| *ratio as u128).sum();
+        if total_ratio == 0 {
+            return Err(MoneyError::InvalidAllocation(format!("{ratios:?}")));
+        }
+        let amount = self.minor.unsigned_abs() as u128;
+        let mut shares: Vec<(u128, u128)> = ratios
+            .iter()
+            .map(|ratio| {
+                let exact = amount * *ratio as u128;
+                (exact / total_ratio, exact % total_ratio)
+            })
+            .collect();
+
+        let allocated: u128 = shares.iter().map(|(share, _)| share).sum();
+        let mut order: Vec<usize> = (0..shares.len()).collect();
+        order.sort_by(|a, b| shares[*b].1.cmp(&shares[*a].1).then(a.cmp(b)));
+        for index in order.into_iter().take((amount - allocated) as usize) {
+            shares[index].0 += 1;
+        }
+
+        let sign: i128 = if self.minor < 0 { -1 } else { 1 };
+        shares
+            .into_iter()
+            .map(|(share, _)| {
+                Ok(Money::from_minor(
+                    to_minor(sign * share as i128)?,
+                    self.currency,
+                ))
+            })
+            .collect()
+    }
+
+    /// Splits the amount into `parts` equal parts, earlier parts get the leftover minor units
+    pub fn split(self, parts: usize) -> Result<Vec<Money>, MoneyError> {
+        if parts == 0 {
+            return Err(MoneyError::InvalidAllocation(String::from("0 parts")));
+        }
+        self.allocate(&vec![1; parts])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    use super::*;
+    use crate::exercises::money::Currency;
+
+    const CASES: usize = 1_000;
+
+    fn minor_units(parts: &[Money]) -> Vec<i64> {
+        parts.iter().map(|part| part.minor()).collect()
+    }
+
+    #[test]
+    fn split_gives_leftover_to_first_parts() {
+        let amount = Money::from_minor(100, Currency::PLN);
+
+        assert_eq!(minor_units(&amount.split(3).unwrap()), vec![34, 33, 33]);
+        assert_eq!(
+            minor_units(&Money::from_minor(-100, Currency::PLN).split(3).unwrap()),
+            vec![-34, -33, -33]
+        );
+        assert!(amount.split(0).is_err());
+    }
+
+    #[test]
+    fn allocate_gives_leftover_to_largest_remainders() {
+        let amount = Money::from_minor(5, Currency::EUR);
+
+        assert_eq!(minor_units(&amount.allocate(&[3, 7]).unwrap()), vec![2, 3]);
+        assert_eq!(minor_units(&amount.allocate(&[2, 7]).unwrap()), vec![1, 4]);
+        assert_eq!(
+            minor_units(&amount.allocate(&[1, 0, 1]).unwrap()),
+            vec![3, 0, 2]
+        );
+        assert!(amount.allocate(&[0, 0]).is_err());
+        assert!(amount.allocate(&[]).is_err());
+    }
+
+    #[test]
+    fn allocated_parts_always_sum_to_the_original() {
+        let mut rng = StdRng::seed_from_u64(32);
+        for _ in 0..CASES {
+            let minor = rng.random_range(i64::MIN..=i64::MAX);
+            let count = rng.random_range(1..=12);
+            let mut ratios: Vec<u32> = (0..count).map(|_| rng.random_range(0..=1_000)).collect();
+            ratios[0] += 1;
+            let amount = Money::from_minor(minor, Currency::USD);
+
+            let parts = amount.allocate(&ratios).unwrap();
+
+            let sum: i128 = parts.iter().map(|part| part.minor() as i128).sum();
+            assert_eq!(sum, minor as i128, "{minor} {ratios:?}");
+            let total_ratio: i128 = ratios.iter().map(|ratio| *ratio as i128).sum();
+            for (part, ratio) in parts.iter().zip(&ratios) {
+                let exact = minor as i128 * *ratio as i128;
+                let difference = (part.minor() as i128 * total_ratio - exact).abs();
+                assert!(difference < total_ratio, "{minor} {ratios:?}");
+                assert_eq!(part.currency(), Currency::USD);
+                if *ratio == 0 {
+                    assert!(part.is_zero());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn split_parts_differ_by_at_most_one_minor_unit() {
+        let mut rng = StdRng::seed_from_u64(33);
+        for _ in 0..CASES {
+            let minor = rng.random_range(-1_000_000_000..=1_000_000_000);
+            let count = rng.random_range(1..=50);
+
+            let parts = minor_units(
+                &Money::from_minor(minor, Currency::PLN)
+                    .split(count)
+                    .unwrap(),
+            );
+
+            assert_eq!(parts.iter().sum::<i64>(), minor);
+            let max = parts.iter().max().unwrap();
+            let min = parts.iter().min().unwrap();
+            assert!(max - min <= 1, "{minor} / {count}: {parts:?}");
+            assert!(parts.windows(2).all(|pair| pair[0].abs() >= pair[1].abs()));
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::iter::Sum;
+
+use chrono::NaiveDate;
+
+use super::{Currency, ExchangeRates, Money, MoneyError, RoundingMode, to_minor};
+
+/// Mixed-currency total, amounts are kept per currency until collapsed with exchange rates.
+/// Sums are kept in i128, so adding any number of `Money` values cannot overflow.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MoneyBag {
+    amounts: BTreeMap<Currency, i128>,
+}
+
+impl MoneyBag {
+    pub fn new() -> Self {
+        MoneyBag::default()
+    }
+
+    pub fn add(&mut self, money: Money) {
+        *self.amounts.entry(money.currency()).or_default() += money.minor() as i128;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.amounts.is_empty()
+    }
+
+    pub fn currencies(&self) -> impl Iterator<Item = Currency> + '_ {
+        self.amounts.keys().copied()
+    }
+
+    /// Total in one currency, zero when the bag holds none of it
+    pub fn get(&self, currency: Currency) -> Result<Money, MoneyError> {
+        let minor = self.amounts.get(&currency).copied().unwrap_or_default();
+        Ok(Money::from_minor(to_minor(minor)?, currency))
+    }
+
+    pub fn amounts(&self) -> Result<Vec<Money>, MoneyError> {
+        self.currencies()
+            .map(|currency| self.get(currency))
+            .collect()
+    }
+
+    /// Converts every currency total at the rate valid on the date and adds them up.
+    /// Each converted total is rounded once, to the minor unit of the target currency.
+    pub fn collapse(
+        &self,
+        rates: &ExchangeRates,
+        to: Currency,
+        date: NaiveDate,
+        rounding: RoundingMode,
+    ) -> Result<Money, MoneyError> {
+        self.amounts()?
+            .into_iter()
+            .try_fold(Money::zero(to), |total, money| {
+                total.checked_add(money.convert(rates, to, date, rounding)?)
+            })
+    }
+}
+
+impl Extend<Money> for MoneyBag {
+    fn extend<T: IntoIterator<Item = Money>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|money| self.add(money));
+    }
+}
+
+impl Sum<Money> for MoneyBag {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Self {
+        let mut bag = MoneyBag::new();
+        bag.extend(iter);
+        bag
+    }
+}
+
+impl<'a> Sum<&'a Money> for MoneyBag {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl Display for MoneyBag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "0");
+        }
+        for (index, currency) in self.currencies().enumerate() {
+            if index > 0 {
+                write!(f, " + ")?;
+            }
+            match self.get(currency) {
+                Ok(money) => write!(f, "{money}")?,
+                Err(_) => write!(f, "{} minor units of {currency}", self.amounts[&currency])?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exercises::money::Rate;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn sums_mixed_currencies_per_currency() {
+        let expenses = [
+            Money::from_minor(1_050, Currency::EUR),
+            Money::from_minor(2_000, Currency::PLN),
+            Money::from_minor(-50, Currency::EUR),
+        ];
+
+        let bag: MoneyBag = expenses.iter().sum();
+
+        assert_eq!(
+            bag.get(Currency::EUR),
+            Ok(Money::from_minor(1_000, Currency::EUR))
+        );
+        assert_eq!(
+            bag.get(Currency::PLN),
+            Ok(Money::from_minor(2_000, Currency::PLN))
+        );
+        assert_eq!(bag.get(Currency::USD), Ok(Money::zero(Currency::USD)));
+        assert_eq!(bag.to_string(), "10.00 EUR + 20.00 PLN");
+    }
+
+    #[test]
+    fn large_sums_do_not_overflow_until_read() {
+        let bag: MoneyBag = [i64::MAX, i64::MAX, -i64::MAX]
+            .into_iter()
+            .map(|minor| Money::from_minor(minor, Currency::USD))
+            .sum();
+
+        assert_eq!(
+            bag.get(Currency::USD),
+            Ok(Money::from_minor(i64::MAX, Currency::USD))
+        );
+        let overflowing: MoneyBag = [Money::from_minor(i64::MAX, Currency::USD); 2].iter().sum();
+        assert_eq!(overflowing.get(Currency::USD), Err(MoneyError::Overflow));
+    }
+
+    #[test]
+    fn collapses_into_one_currency() {
+        let mut rates = ExchangeRates::default();
+        rates.add(
+            date("2026-05-01"),
+            Currency::EUR,
+            Currency::PLN,
+            Rate::parse("4.3").unwrap(),
+        );
+        let bag: MoneyBag = [
+            Money::from_minor(1_000, Currency::EUR),
+            Money::from_minor(700, Currency::PLN),
+        ]
+        .into_iter()
+        .sum();
+
+        let total = bag.collapse(
+            &rates,
+            Currency::PLN,
+            date("2026-05-02"),
+            RoundingMode::HalfEven,
+        );
+
+        assert_eq!(total, Ok(Money::from_minor(5_000, Currency::PLN)));
+        assert!(
+            bag.collapse(
+                &rates,
+                Currency::USD,
+                date("2026-05-02"),
+                RoundingMode::HalfEven
+            )
+            .is_err()
+        );
+    }
+}
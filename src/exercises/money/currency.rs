@@ -0,0 +1,140 @@
+use std::fmt::Display;
+
+use super::MoneyError;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Currency {
+    code: &'static str,
+    numeric: u16,
+    minor_units: u32,
+    name: &'static str,
+}
+
+impl Currency {
+    pub const AED: Currency = Currency::new("AED", 784, 2, "UAE Dirham");
+    pub const AUD: Currency = Currency::new("AUD", 36, 2, "Australian Dollar");
+    pub const BHD: Currency = Currency::new("BHD", 48, 3, "Bahraini Dinar");
+    pub const BRL: Currency = Currency::new("BRL", 986, 2, "Brazilian Real");
+    pub const CAD: Currency = Currency::new("CAD", 124, 2, "Canadian Dollar");
+    pub const CHF: Currency = Currency::new("CHF", 756, 2, "Swiss Franc");
+    pub const CLP: Currency = Currency::new("CLP", 152, 0, "Chilean Peso");
+    pub const CNY: Currency = Currency::new("CNY", 156, 2, "Yuan Renminbi");
+    pub const CZK: Currency = Currency::new("CZK", 203, 2, "Czech Koruna");
+    pub const DKK: Currency = Currency::new("DKK", 208, 2, "Danish Krone");
+    pub const EUR: Currency = Currency::new("EUR", 978, 2, "Euro");
+    pub const GBP: Currency = Currency::new("GBP", 826, 2, "Pound Sterling");
+    pub const HKD: Currency = Currency::new("HKD", 344, 2, "Hong Kong Dollar");
+    pub const HUF: Currency = Currency::new("HUF", 348, 2, "Forint");
+    pub const INR: Currency = Currency::new("INR", 356, 2, "Indian Rupee");
+    pub const ISK: Currency = Currency::new("ISK", 352, 0, "Iceland Krona");
+    pub const JOD: Currency = Currency::new("JOD", 400, 3, "Jordanian Dinar");
+    pub const JPY: Currency = Currency::new("JPY", 392, 0, "Yen");
+    pub const KRW: Currency = Currency::new("KRW", 410, 0, "Won");
+    pub const KWD: Currency = Currency::new("KWD", 414, 3, "Kuwaiti Dinar");
+    pub const MXN: Currency = Currency::new("MXN", 484, 2, "Mexican Peso");
+    pub const NOK: Currency = Currency::new("NOK", 578, 2, "Norwegian Krone");
+    pub const NZD: Currency = Currency::new("NZD", 554, 2, "New Zealand Dollar");
+    pub const OMR: Currency = Currency::new("OMR", 512, 3, "Rial Omani");
+    pub const PLN: Currency = Currency::new("PLN", 985, 2, "Zloty");
+    pub const RON: Currency = Currency::new("RON", 946, 2, "Romanian Leu");
+    pub const SEK: Currency = Currency::new("SEK", 752, 2, "Swedish Krona");
+    pub const SGD: Currency = Currency::new("SGD", 702, 2, "Singapore Dollar");
+    pub const TND: Currency = Currency::new("TND", 788, 3, "Tunisian Dinar");
+    pub const TRY: Currency = Currency::new("TRY", 949, 2, "Turkish Lira");
+    pub const UAH: Currency = Currency::new("UAH", 980, 2, "Hryvnia");
+    pub const USD: Currency = Currency::new("USD", 840, 2, "US Dollar");
+    pub const ZAR: Currency = Currency::new("ZAR", 710, 2, "Rand");
+
+    const fn new(code: &'static str, numeric: u16, minor_units: u32, name: &'static str) -> Self {
+        Currency {
+            code,
+            numeric,
+            minor_units,
+            name,
+        }
+    }
+
+    pub fn from_code(code: &str) -> Result<Currency, MoneyError> {
+        let code = code.trim();
+        CURRENCIES
+            .iter()
+            .find(|currency| currency.code.eq_ignore_ascii_case(code))
+            .copied()
+            .ok_or_else(|| MoneyError::UnknownCurrency(code.to_string()))
+    }
+
+    pub fn from_numeric(numeric: u16) -> Result<Currency, MoneyError> {
+        CURRENCIES
+            .iter()
+            .find(|currency| currency.numeric == numeric)
+            .copied()
+            .ok_or_else(|| MoneyError::UnknownCurrency(format!("{numeric:03}")))
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn numeric(&self) -> u16 {
+        self.numeric
+    }
+
+    /// Number of decimal places of the minor unit, e.g. 2 for cents, 0 for yen
+    pub fn minor_units(&self) -> u32 {
+        self.minor_units
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl TryFrom<&str> for Currency {
+    type Error = MoneyError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Currency::from_code(value)
+    }
+}
+
+pub const CURRENCIES: &[Currency] = &[
+    Currency::AED,
+    Currency::AUD,
+    Currency::BHD,
+    Currency::BRL,
+    Currency::CAD,
+    Currency::CHF,
+    Currency::CLP,
+    Currency::CNY,
+    Currency::CZK,
+    Currency::DKK,
+    Currency::EUR,
+    Currency::GBP,
+    Currency::HKD,
+    Currency::HUF,
+    Currency::INR,
+    Currency::ISK,
+    Currency::JOD,
+    Currency::JPY,
+    Currency::KRW,
+    Currency::KWD,
+    Currency::MXN,
+    Currency::NOK,
+    Currency::NZD,
+    Currency::OMR,
+    Currency::PLN,
+    Currency::RON,
+    Currency::SEK,
+    Currency::SGD,
+    Currency::TND,
+    Currency::TRY,
+    Currency::UAH,
+    Currency::USD,
+    Currency::ZAR,
+];
@@ -0,0 +1,340 @@
+use std::fmt::Display;
+
+use super::{Currency, Money, MoneyError, RoundingMode};
+
+// Separators accepted when parsing in addition to the locale's own group separator
+const SPACES: [char; 3] = [' ', '\u{a0}', '\u{202f}'];
+
+// Local symbols are used only in their own locale, other locales show the ISO code
+const SYMBOLS: [(Currency, &str, Option<Locale>); 5] = [
+    (Currency::PLN, "zł", Some(Locale::PlPl)),
+    (Currency::EUR, "€", None),
+    (Currency::USD, "$", None),
+    (Currency::GBP, "£", None),
+    (Currency::JPY, "¥", None),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Locale {
+    PlPl,
+    EnUs,
+    DeDe,
+}
+
+struct LocaleRules {
+    group: char,
+    decimal: char,
+    symbol_first: bool,
+}
+
+impl Locale {
+    fn rules(&self) -> LocaleRules {
+        match self {
+            Locale::PlPl => LocaleRules {
+                group: ' ',
+                decimal: ',',
+                symbol_first: false,
+            },
+            Locale::EnUs => LocaleRules {
+                group: ',',
+                decimal: '.',
+                symbol_first: true,
+            },
+            Locale::DeDe => LocaleRules {
+                group: '.',
+                decimal: ',',
+                symbol_first: false,
+            },
+        }
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tag = match self {
+            Locale::PlPl => "pl-PL",
+            Locale::EnUs => "en-US",
+            Locale::DeDe => "de-DE",
+        };
+        write!(f, "{tag}")
+    }
+}
+
+impl TryFrom<&str> for Locale {
+    type Error = MoneyError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_lowercase().replace('_', "-").as_str() {
+            "pl-pl" | "pl" => Ok(Locale::PlPl),
+            "en-us" | "en" => Ok(Locale::EnUs),
+            "de-de" | "de" => Ok(Locale::DeDe),
+            _ => Err(MoneyError::UnknownLocale(value.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum NegativeStyle {
+    /// "-1 234,56 zł"
+    #[default]
+    Minus,
+    /// "(1 234,56 zł)"
+    Accounting,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MoneyFormatter {
+    locale: Locale,
+    negative: NegativeStyle,
+}
+
+fn symbol(currency: Currency, locale: Locale) -> &'static str {
+    SYMBOLS
+        .iter()
+        .find(|(known, _, only_in)| {
+            *known == currency && only_in.is_none_or(|only_in| only_in == locale)
+        })
+        .map_or(currency.code(), |(_, symbol, _)| symbol)
+}
+
+fn group_digits(digits: &str, separator: char) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+impl MoneyFormatter {
+    pub fn new(locale: Locale) -> Self {
+        MoneyFormatter {
+            locale,
+            negative: NegativeStyle::default(),
+        }
+    }
+
+    pub fn with_negative_style(mut self, negative: NegativeStyle) -> Self {
+        self.negative = negative;
+        self
+    }
+
+    pub fn format(&self, money: Money) -> String {
+        let rules = self.locale.rules();
+        let decimal = money.to_decimal_string();
+        let unsigned = decimal.trim_start_matches('-');
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let mut number = group_digits(integer, rules.group);
+        if !fraction.is_empty() {
+            number.push(rules.decimal);
+            number.push_str(fraction);
+        }
+
+        let symbol = symbol(money.currency(), self.locale);
+        let is_code = symbol == money.currency().code();
+        let body = match (rules.symbol_first, is_code) {
+            (true, false) => format!("{symbol}{number}"),
+            (true, true) => format!("{symbol} {number}"),
+            (false, _) => format!("{number} {symbol}"),
+        };
+        match (money.is_negative(), self.negative) {
+            (false, _) => body,
+            (true, NegativeStyle::Minus) => format!("-{body}"),
+            (true, NegativeStyle::Accounting) => format!("({body})"),
+        }
+    }
+
+    /// Accepts the forms produced by `format` in both negative styles
+    pub fn parse(&self, text: &str) -> Result<Money, MoneyError> {
+        let invalid = || MoneyError::InvalidAmount(text.to_string());
+        let rules = self.locale.rules();
+        let mut rest = text.trim();
+        let mut negative = false;
+        if let Some(inner) = rest
+            .strip_prefix('(')
+            .and_then(|inner| inner.strip_suffix(')'))
+        {
+            rest = inner.trim();
+            negative = true;
+        }
+        if let Some(unsigned) = rest.strip_prefix('-') {
+            if negative {
+                return Err(invalid());
+            }
+            rest = unsigned.trim_start();
+            negative = true;
+        }
+
+        let (currency, number) = split_currency(rest).ok_or_else(invalid)?;
+        let number = match number.strip_prefix('-') {
+            Some(unsigned) if !negative => {
+                negative = true;
+                unsigned
+            }
+            _ => number,
+        };
+        let mut plain = String::with_capacity(number.len());
+        for character in number.chars() {
+            match character {
+                c if c.is_ascii_digit() => plain.push(c),
+                c if c == rules.decimal => plain.push('.'),
+                c if c == rules.group || SPACES.contains(&c) => {}
+                _ => return Err(invalid()),
+            }
+        }
+        let fraction_digits = plain
+            .split_once('.')
+            .map_or(0, |(_, fraction)| fraction.len());
+        if plain.is_empty() || fraction_digits as u32 > currency.minor_units() {
+            return Err(invalid());
+        }
+        let money = Money::from_decimal(&plain, currency, RoundingMode::HalfEven)?;
+        if negative {
+            money.checked_neg()
+        } else {
+            Ok(money)
+        }
+    }
+}
+
+// Finds a currency symbol or ISO code at either end of the text
+fn split_currency(text: &str) -> Option<(Currency, &str)> {
+    let symbols = SYMBOLS
+        .iter()
+        .map(|(currency, symbol, _)| (*currency, *symbol))
+        .chain(
+            super::currency::CURRENCIES
+                .iter()
+                .map(|currency| (*currency, currency.code())),
+        );
+    for (currency, symbol) in symbols {
+        if let Some(rest) = text.strip_prefix(symbol) {
+            return Some((currency, rest.trim()));
+        }
+        if let Some(rest) = text.strip_suffix(symbol) {
+            return Some((currency, rest.trim()));
+        }
+    }
+    None
+}
+
+impl Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.to_decimal_string(), self.currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    use super::*;
+
+    const LOCALES: [Locale; 3] = [Locale::PlPl, Locale::EnUs, Locale::DeDe];
+    const STYLES: [NegativeStyle; 2] = [NegativeStyle::Minus, NegativeStyle::Accounting];
+
+    fn formatter(locale: Locale, negative: NegativeStyle) -> MoneyFormatter {
+        MoneyFormatter::new(locale).with_negative_style(negative)
+    }
+
+    #[test]
+    fn formats_amounts_for_each_locale() {
+        let amount = Money::from_minor(123_456, Currency::PLN);
+        let dollars = Money::from_minor(123_456, Currency::USD);
+        let euros = Money::from_minor(-123_456, Currency::EUR);
+
+        assert_eq!(
+            MoneyFormatter::new(Locale::PlPl).format(amount),
+            "1 234,56 zł"
+        );
+        assert_eq!(
+            MoneyFormatter::new(Locale::EnUs).format(dollars),
+            "$1,234.56"
+        );
+        assert_eq!(
+            MoneyFormatter::new(Locale::DeDe).format(euros),
+            "-1.234,56 €"
+        );
+    }
+
+    #[test]
+    fn formats_accounting_negatives_and_currency_codes() {
+        let accounting = formatter(Locale::EnUs, NegativeStyle::Accounting);
+
+        assert_eq!(
+            accounting.format(Money::from_minor(-5, Currency::USD)),
+            "($0.05)"
+        );
+        assert_eq!(
+            accounting.format(Money::from_minor(1_000_000, Currency::CHF)),
+            "CHF 10,000.00"
+        );
+        assert_eq!(
+            MoneyFormatter::new(Locale::PlPl).format(Money::from_minor(1_234_567, Currency::JPY)),
+            "1 234 567 ¥"
+        );
+        assert_eq!(
+            accounting.format(Money::from_minor(-123_456, Currency::PLN)),
+            "(PLN 1,234.56)"
+        );
+        assert_eq!(
+            Money::from_minor(-12_345, Currency::KWD).to_string(),
+            "-12.345 KWD"
+        );
+    }
+
+    #[test]
+    fn parses_user_written_variants() {
+        let polish = MoneyFormatter::new(Locale::PlPl);
+
+        assert_eq!(
+            polish.parse("1\u{a0}234,5 zł"),
+            Ok(Money::from_minor(123_450, Currency::PLN))
+        );
+        assert_eq!(
+            polish.parse("-12 PLN"),
+            Ok(Money::from_minor(-1_200, Currency::PLN))
+        );
+        assert_eq!(
+            MoneyFormatter::new(Locale::EnUs).parse("-$3"),
+            Ok(Money::from_minor(-300, Currency::USD))
+        );
+        assert!(polish.parse("1 234,567 zł").is_err());
+        assert!(polish.parse("1 234,56").is_err());
+        assert!(polish.parse("-(5 zł)").is_err());
+        assert!(polish.parse("zł").is_err());
+    }
+
+    #[test]
+    fn formatted_amounts_parse_back() {
+        let mut rng = StdRng::seed_from_u64(33);
+        let currencies = [
+            Currency::PLN,
+            Currency::USD,
+            Currency::EUR,
+            Currency::JPY,
+            Currency::KWD,
+            Currency::CHF,
+        ];
+        for _ in 0..500 {
+            let currency = currencies[rng.random_range(0..currencies.len())];
+            let money =
+                Money::from_minor(rng.random_range(-10_000_000_000..=10_000_000_000), currency);
+            for locale in LOCALES {
+                for style in STYLES {
+                    let formatter = formatter(locale, style);
+                    let text = formatter.format(money);
+
+                    assert_eq!(
+                        formatter.parse(&text),
+                        Ok(money),
+                        "{locale} {style:?} {text}"
+                    );
+                }
+            }
+        }
+    }
+}
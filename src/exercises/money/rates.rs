@@ -0,0 +1,437 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::path::Path;
+
+use chrono::NaiveDate;
+
+use super::{Currency, MoneyError, RoundingMode};
+
+const DISPLAY_DIGITS: u32 = 6;
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// Exchange rate kept as an exact fraction, so derived (inverse and cross) rates lose nothing
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rate {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rate {
+    fn new(numerator: i128, denominator: i128) -> Result<Rate, MoneyError> {
+        if numerator <= 0 || denominator <= 0 {
+            return Err(MoneyError::InvalidRate(format!(
+                "{numerator}/{denominator}"
+            )));
+        }
+        let divisor = gcd(numerator, denominator);
+        Ok(Rate {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    pub fn parse(value: &str) -> Result<Rate, MoneyError> {
+        let invalid = || MoneyError::InvalidRate(value.to_string());
+        let value = value.trim();
+        let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+        if integer.is_empty() && fraction.is_empty()
+            || !integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let numerator: i128 = format!("{integer}{fraction}")
+            .parse()
+            .map_err(|_| invalid())?;
+        let denominator = 10_i128
+            .checked_pow(fraction.len() as u32)
+            .ok_or_else(invalid)?;
+        Rate::new(numerator, denominator).map_err(|_| invalid())
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn inverse(self) -> Rate {
+        Rate {
+            numerator: self.denominator,
+            denominator: self.numerator,
+        }
+    }
+
+    /// Chains two conversions, e.g. EUR→PLN then PLN→USD gives EUR→USD
+    pub fn then(self, next: Rate) -> Result<Rate, MoneyError> {
+        let numerator = self
+            .numerator
+            .checked_mul(next.numerator)
+            .ok_or(MoneyError::Overflow)?;
+        let denominator = self
+            .denominator
+            .checked_mul(next.denominator)
+            .ok_or(MoneyError::Overflow)?;
+        Rate::new(numerator, denominator)
+    }
+}
+
+impl Display for Rate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scale = 10_i128.pow(DISPLAY_DIGITS);
+        // A numerator too large to scale is shown as the exact fraction
+        let Some(scaled) = self.numerator.checked_mul(scale) else {
+            return write!(f, "{}/{}", self.numerator, self.denominator);
+        };
+        let scaled = RoundingMode::HalfEven.divide(scaled, self.denominator);
+        write!(
+            f,
+            "{}.{:0width$}",
+            scaled / scale,
+            scaled % scale,
+            width = DISPLAY_DIGITS as usize
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Quote {
+    date: NaiveDate,
+    from: Currency,
+    to: Currency,
+    rate: Rate,
+}
+
+/// Registry of quoted rates, each valid from its date until a newer quote for the same pair
+#[derive(Default)]
+pub struct ExchangeRates {
+    quotes: Vec<Quote>,
+}
+
+impl ExchangeRates {
+    pub fn add(&mut self, date: NaiveDate, from: Currency, to: Currency, rate: Rate) {
+        self.quotes.push(Quote {
+            date,
+            from,
+            to,
+            rate,
+        });
+    }
+
+    /// Loads a `.json` or `.csv` file with date, from, to and rate fields
+    pub fn load(path: &Path) -> Result<ExchangeRates, MoneyError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| MoneyError::InvalidRateFile(format!("{}: {e}", path.display())))?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => ExchangeRates::from_json(&content),
+            _ => ExchangeRates::from_csv(&content),
+        }
+    }
+
+    pub fn from_csv(content: &str) -> Result<ExchangeRates, MoneyError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(content.as_bytes());
+        let headers = reader
+            .headers()
+            .map_err(|e| MoneyError::InvalidRateFile(e.to_string()))?
+            .clone();
+        let mut rates = ExchangeRates::default();
+        for (index, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| MoneyError::InvalidRateFile(e.to_string()))?;
+            let fields: HashMap<String, String> = headers
+                .iter()
+                .zip(record.iter())
+                .map(|(header, value)| (header.to_lowercase(), value.to_string()))
+                .collect();
+            rates
+                .add_fields(&fields)
+                .map_err(|e| MoneyError::InvalidRateFile(format!("row {}: {e}", index + 1)))?;
+        }
+        Ok(rates)
+    }
+
+    pub fn from_json(content: &str) -> Result<ExchangeRates, MoneyError> {
+        let objects = json::parse_objects(content).map_err(MoneyError::InvalidRateFile)?;
+        let mut rates = ExchangeRates::default();
+        for (index, fields) in objects.iter().enumerate() {
+            rates
+                .add_fields(fields)
+                .map_err(|e| MoneyError::InvalidRateFile(format!("entry {}: {e}", index + 1)))?;
+        }
+        Ok(rates)
+    }
+
+    fn add_fields(&mut self, fields: &HashMap<String, String>) -> Result<(), MoneyError> {
+        let field = |name: &str| {
+            fields
+                .get(name)
+                .ok_or_else(|| MoneyError::InvalidRateFile(format!("missing field: {name}")))
+        };
+        let date = field("date")?;
+        let date = date
+            .parse()
+            .map_err(|_| MoneyError::InvalidRateFile(format!("invalid date: {date}")))?;
+        let from = Currency::from_code(field("from")?)?;
+        let to = Currency::from_code(field("to")?)?;
+        let rate = Rate::parse(field("rate")?)?;
+        self.add(date, from, to, rate);
+        Ok(())
+    }
+
+    // Latest quote on or before the date for every quoted pair
+    fn effective(&self, date: NaiveDate) -> BTreeMap<(Currency, Currency), &Quote> {
+        let mut effective: BTreeMap<(Currency, Currency), &Quote> = BTreeMap::new();
+        for quote in self.quotes.iter().filter(|quote| quote.date <= date) {
+            let entry = effective.entry((quote.from, quote.to)).or_insert(quote);
+            if quote.date >= entry.date {
+                *entry = quote;
+            }
+        }
+        effective
+    }
+
+    /// Resolves a direct quote, the inverse of a quote or a chain of them (cross rate)
+    pub fn rate(&self, from: Currency, to: Currency, date: NaiveDate) -> Result<Rate, MoneyError> {
+        if from == to {
+            return Rate::new(1, 1);
+        }
+        let effective = self.effective(date);
+        let mut edges: BTreeMap<Currency, Vec<(Currency, Rate)>> = BTreeMap::new();
+        for ((quote_from, quote_to), quote) in &effective {
+            edges
+                .entry(*quote_from)
+                .or_default()
+                .push((*quote_to, quote.rate));
+            if !effective.contains_key(&(*quote_to, *quote_from)) {
+                edges
+                    .entry(*quote_to)
+                    .or_default()
+                    .push((*quote_from, quote.rate.inverse()));
+            }
+        }
+
+        // Breadth-first search finds the path with the fewest conversions
+        let mut visited = HashSet::from([from]);
+        let mut queue = VecDeque::from([(from, Rate::new(1, 1)?)]);
+        while let Some((currency, rate)) = queue.pop_front() {
+            for (next, next_rate) in edges.get(&currency).into_iter().flatten() {
+                if !visited.insert(*next) {
+                    continue;
+                }
+                let combined = rate.then(*next_rate)?;
+                if *next == to {
+                    return Ok(combined);
+                }
+                queue.push_back((*next, combined));
+            }
+        }
+        Err(MoneyError::MissingRate { from, to, date })
+    }
+}
+
+mod json {
+    use std::collections::HashMap;
+    use std::iter::Peekable;
+    use std::str::Chars;
+
+    // Reads an array of flat objects, values are kept as text so decimal rates stay exact
+    pub fn parse_objects(content: &str) -> Result<Vec<HashMap<String, String>>, String> {
+        let mut chars = content.chars().peekable();
+        expect(&mut chars, '[')?;
+        let mut objects = Vec::new();
+        if peek(&mut chars) == Some(']') {
+            chars.next();
+            return Ok(objects);
+        }
+        loop {
+            objects.push(parse_object(&mut chars)?);
+            match next(&mut chars) {
+                Some(',') => continue,
+                Some(']') => break,
+                other => return Err(format!("expected ',' or ']', found {other:?}")),
+            }
+        }
+        match peek(&mut chars) {
+            None => Ok(objects),
+            Some(other) => Err(format!("unexpected trailing character: {other}")),
+        }
+    }
+
+    fn parse_object(chars: &mut Peekable<Chars>) -> Result<HashMap<String, String>, String> {
+        expect(chars, '{')?;
+        let mut fields = HashMap::new();
+        if peek(chars) == Some('}') {
+            chars.next();
+            return Ok(fields);
+        }
+        loop {
+            expect(chars, '"')?;
+            let key = parse_string(chars)?;
+            expect(chars, ':')?;
+            let value = match peek(chars) {
+                Some('"') => {
+                    chars.next();
+                    parse_string(chars)?
+                }
+                _ => parse_literal(chars),
+            };
+            fields.insert(key.to_lowercase(), value);
+            match next(chars) {
+                Some(',') => continue,
+                Some('}') => return Ok(fields),
+                other => return Err(format!("expected ',' or '}}', found {other:?}")),
+            }
+        }
+    }
+
+    fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+        let mut value = String::new();
+        loop {
+            match chars.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(other) => value.push(other),
+                    None => return Err(String::from("unterminated string")),
+                },
+                Some(other) => value.push(other),
+                None => return Err(String::from("unterminated string")),
+            }
+        }
+    }
+
+    fn parse_literal(chars: &mut Peekable<Chars>) -> String {
+        let mut value = String::new();
+        while let Some(c) = chars.peek() {
+            if c.is_whitespace() || matches!(c, ',' | '}' | ']') {
+                break;
+            }
+            value.push(*c);
+            chars.next();
+        }
+        value
+    }
+
+    fn skip_whitespace(chars: &mut Peekable<Chars>) {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek(chars: &mut Peekable<Chars>) -> Option<char> {
+        skip_whitespace(chars);
+        chars.peek().copied()
+    }
+
+    fn next(chars: &mut Peekable<Chars>) -> Option<char> {
+        skip_whitespace(chars);
+        chars.next()
+    }
+
+    fn expect(chars: &mut Peekable<Chars>, expected: char) -> Result<(), String> {
+        match next(chars) {
+            Some(c) if c == expected => Ok(()),
+            other => Err(format!("expected '{expected}', found {other:?}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exercises::money::Money;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn rates() -> ExchangeRates {
+        ExchangeRates::from_csv(
+            "date,from,to,rate\n\
+             2026-04-01,EUR,PLN,4.25\n\
+             2026-05-01,EUR,PLN,4.30\n\
+             2026-05-01,USD,PLN,4.00\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rate_depends_on_effective_date() {
+        let rates = rates();
+
+        assert_eq!(
+            rates.rate(Currency::EUR, Currency::PLN, date("2026-04-30")),
+            Rate::parse("4.25")
+        );
+        assert_eq!(
+            rates.rate(Currency::EUR, Currency::PLN, date("2026-06-15")),
+            Rate::parse("4.3")
+        );
+        assert!(
+            rates
+                .rate(Currency::EUR, Currency::PLN, date("2026-03-31"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn inverse_and_cross_rates_are_derived() {
+        let rates = rates();
+        let day = date("2026-05-01");
+
+        let inverse = rates.rate(Currency::PLN, Currency::EUR, day).unwrap();
+        let cross = rates.rate(Currency::EUR, Currency::USD, day).unwrap();
+
+        assert_eq!(inverse, Rate::parse("4.3").unwrap().inverse());
+        assert_eq!(cross.to_string(), "1.075000");
+        assert_eq!(
+            rates.rate(Currency::EUR, Currency::JPY, day),
+            Err(MoneyError::MissingRate {
+                from: Currency::EUR,
+                to: Currency::JPY,
+                date: day
+            })
+        );
+    }
+
+    #[test]
+    fn json_rates_match_csv_rates() {
+        let rates = ExchangeRates::from_json(
+            r#"[
+                {"date": "2026-05-01", "from": "EUR", "to": "PLN", "rate": 4.30},
+                {"date": "2026-05-01", "from": "USD", "to": "PLN", "rate": "4.00"}
+            ]"#,
+        )
+        .unwrap();
+
+        let converted = Money::from_major(100, Currency::EUR)
+            .unwrap()
+            .convert(
+                &rates,
+                Currency::USD,
+                date("2026-05-02"),
+                RoundingMode::HalfEven,
+            )
+            .unwrap();
+
+        assert_eq!(converted, Money::from_minor(10750, Currency::USD));
+        assert!(ExchangeRates::from_json("[{\"date\": \"2026-05-01\"}").is_err());
+    }
+    #[test]
+    fn huge_rates_are_displayed_as_fractions() {
+        let huge = Rate::parse(&format!("{}.3", i128::MAX / 100)).unwrap();
+
+        assert_eq!(Rate::parse("0.5").unwrap().to_string(), "0.500000");
+        assert_eq!(huge.to_string(), format!("{}/10", i128::MAX / 100 * 10 + 3));
+    }
+}
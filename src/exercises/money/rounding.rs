@@ -0,0 +1,41 @@
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RoundingMode {
+    /// Ties go to the even neighbour (banker's rounding)
+    #[default]
+    HalfEven,
+    /// Ties go away from zero
+    HalfUp,
+    /// Towards negative infinity
+    Floor,
+    /// Towards positive infinity
+    Ceil,
+}
+
+impl RoundingMode {
+    /// Divides `value` by `divisor` rounding the quotient, `divisor` must not be zero
+    pub fn divide(&self, value: i128, divisor: i128) -> i128 {
+        let (value, divisor) = if divisor < 0 {
+            (-value, -divisor)
+        } else {
+            (value, divisor)
+        };
+        let quotient = value / divisor;
+        let remainder = value % divisor;
+        if remainder == 0 {
+            return quotient;
+        }
+        let away_from_zero = quotient + value.signum();
+        let twice_remainder = remainder.abs() * 2;
+        match self {
+            RoundingMode::Floor if remainder < 0 => quotient - 1,
+            RoundingMode::Ceil if remainder > 0 => quotient + 1,
+            RoundingMode::Floor | RoundingMode::Ceil => quotient,
+            RoundingMode::HalfUp if twice_remainder >= divisor => away_from_zero,
+            RoundingMode::HalfEven if twice_remainder > divisor => away_from_zero,
+            RoundingMode::HalfEven if twice_remainder == divisor && quotient % 2 != 0 => {
+                away_from_zero
+            }
+            RoundingMode::HalfUp | RoundingMode::HalfEven => quotient,
+        }
+    }
+}
@@ -1,13 +1,40 @@
+use std::fmt::Display;
 use std::ops::Add;
 
-#[derive(PartialEq, Debug, Clone)]
-enum Currency {
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Default)]
+pub enum Currency {
+    #[default]
     Pln,
     Eur,
+    Usd,
 }
 
-#[derive(Debug)]
-struct MonetaryAmount {
+impl Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Currency::Pln => "PLN",
+            Currency::Eur => "EUR",
+            Currency::Usd => "USD",
+        };
+        write!(f, "{code}")
+    }
+}
+
+impl TryFrom<&str> for Currency {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_uppercase().as_str() {
+            "PLN" => Ok(Currency::Pln),
+            "EUR" => Ok(Currency::Eur),
+            "USD" => Ok(Currency::Usd),
+            _ => Err(format!("Unknown currency: {value}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MonetaryAmount {
     value: f64,
     currency: Currency,
 }
@@ -36,19 +63,33 @@ impl MonetaryAmount {
         Ok(self)
     }
 
-    fn convert(amount: &MonetaryAmount, exchange_rate: f64, currency: &Currency) -> Self {
+    pub fn convert(amount: &MonetaryAmount, exchange_rate: f64, currency: &Currency) -> Self {
         Self {
             value: amount.value / exchange_rate,
             currency: currency.clone(),
         }
     }
 
-    fn new(value: f64, currency: &Currency) -> Self {
+    pub fn new(value: f64, currency: &Currency) -> Self {
         Self {
             value,
             currency: currency.clone(),
         }
     }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn currency(&self) -> &Currency {
+        &self.currency
+    }
+}
+
+impl Display for MonetaryAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.2} {}", self.value, self.currency)
+    }
 }
 
 impl Add for MonetaryAmount {
@@ -1,14 +1,42 @@
+mod ai;
+mod input;
+mod network;
+mod notation;
+mod tui;
+
 use std::fmt::Display;
-use std::io;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+use rand::Rng;
 
-const BOARD_SIZE: usize = 3;
+use ai::{Ai, Difficulty};
+use input::{HELP, Input, parse_input};
+use network::{Connection, host_game, join_game};
+use notation::{cell_name, load, save};
 
-#[derive(Copy, Clone, PartialEq)]
+const DEFAULT_SIZE: usize = 3;
+const MAX_SIZE: usize = 26;
+const DEFAULT_PORT: u16 = 7000;
+const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+
+#[derive(clap::ValueEnum, Copy, Clone, PartialEq, Debug)]
 enum Player {
     X,
     O,
 }
 
+impl Player {
+    fn opponent(&self) -> Player {
+        match self {
+            Player::X => Player::O,
+            Player::O => Player::X,
+        }
+    }
+}
+
 impl Display for Player {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let symbol = match self {
@@ -19,7 +47,7 @@ impl Display for Player {
     }
 }
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 enum Cell {
     Empty,
     Taken(Player),
@@ -34,130 +62,552 @@ impl Display for Cell {
     }
 }
 
+/// Board of `width` × `height` cells where `win_length` marks in a row win,
+/// cells are stored row by row and `moves` keeps the played cells in order
+#[derive(Clone)]
 struct TicTacToe {
-    board: [[Cell; BOARD_SIZE]; BOARD_SIZE],
+    width: usize,
+    height: usize,
+    win_length: usize,
+    board: Vec<Cell>,
+    taken: usize,
     player: Player,
+    moves: Vec<(usize, usize)>,
 }
 
 impl TicTacToe {
-    fn new() -> TicTacToe {
+    fn new(width: usize, height: usize, win_length: usize) -> TicTacToe {
         TicTacToe {
-            board: [[Cell::Empty; BOARD_SIZE]; BOARD_SIZE],
+            width,
+            height,
+            win_length,
+            board: vec![Cell::Empty; width * height],
+            taken: 0,
             player: Player::X,
+            moves: Vec::new(),
         }
     }
 
     fn toggle_player(&mut self) {
-        match self.player {
-            Player::X => self.player = Player::O,
-            Player::O => self.player = Player::X,
-        }
+        self.player = self.player.opponent();
     }
 
-    fn display(&self) {
-        for row in self.board.iter() {
-            for cell in row.iter() {
-                print!("{cell}");
-            }
-            println!();
-        }
+    fn cell(&self, col: usize, row: usize) -> Cell {
+        self.board[row * self.width + col]
     }
 
     fn make_move(&mut self, col: usize, row: usize, player: Player) -> bool {
         if self.is_valid_move(col, row) {
-            self.board[row][col] = Cell::Taken(player);
+            self.board[row * self.width + col] = Cell::Taken(player);
+            self.taken += 1;
             true
         } else {
             false
         }
     }
 
+    /// Current player's move, recorded in the history before the turn passes to the opponent
+    fn play(&mut self, col: usize, row: usize) -> bool {
+        if !self.make_move(col, row, self.player) {
+            return false;
+        }
+        self.moves.push((col, row));
+        self.toggle_player();
+        true
+    }
+
+    /// Takes back the last move, its player is to move again
+    fn undo(&mut self) -> Option<(usize, usize)> {
+        let (col, row) = self.moves.pop()?;
+        self.clear(col, row);
+        self.toggle_player();
+        Some((col, row))
+    }
+
+    fn clear(&mut self, col: usize, row: usize) {
+        if self.cell(col, row) != Cell::Empty {
+            self.board[row * self.width + col] = Cell::Empty;
+            self.taken -= 1;
+        }
+    }
+
+    fn empty_cells(&self) -> Vec<(usize, usize)> {
+        (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (col, row)))
+            .filter(|(col, row)| self.cell(*col, *row) == Cell::Empty)
+            .collect()
+    }
+
     fn is_valid_move(&self, col: usize, row: usize) -> bool {
-        row < BOARD_SIZE && col < BOARD_SIZE && self.board[row][col] == Cell::Empty
+        row < self.height && col < self.width && self.cell(col, row) == Cell::Empty
     }
 
     fn is_board_full(&self) -> bool {
-        for row in &self.board {
-            for cell in row {
-                if *cell == Cell::Empty {
-                    return false;
+        self.taken == self.board.len()
+    }
+
+    /// Player who made a winning line with the last move
+    fn winner(&self) -> Option<Player> {
+        let &(col, row) = self.moves.last()?;
+        match self.cell(col, row) {
+            Cell::Taken(player) if self.is_winning_move(col, row) => Some(player),
+            _ => None,
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.outcome().is_some()
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        match self.winner() {
+            Some(winner) => Some(Outcome::Won(winner)),
+            None if self.is_board_full() => Some(Outcome::Draw),
+            None => None,
+        }
+    }
+
+    /// Whether the mark at the cell is part of a winning line, only the lines
+    /// through that cell are checked so it is meant to be called after each move
+    fn is_winning_move(&self, col: usize, row: usize) -> bool {
+        let Cell::Taken(player) = self.cell(col, row) else {
+            return false;
+        };
+        DIRECTIONS.iter().any(|&(dx, dy)| {
+            1 + self.run_length(col, row, dx, dy, player)
+                + self.run_length(col, row, -dx, -dy, player)
+                >= self.win_length
+        })
+    }
+
+    /// Cells of the lines completed by the last move, empty while nobody has won
+    fn winning_line(&self) -> Vec<(usize, usize)> {
+        let Some(&(col, row)) = self.moves.last() else {
+            return Vec::new();
+        };
+        let Cell::Taken(player) = self.cell(col, row) else {
+            return Vec::new();
+        };
+        let mut cells = Vec::new();
+        for &(dx, dy) in &DIRECTIONS {
+            let forward = self.run_length(col, row, dx, dy, player);
+            let backward = self.run_length(col, row, -dx, -dy, player);
+            if 1 + forward + backward < self.win_length {
+                continue;
+            }
+            for step in -(backward as isize)..=forward as isize {
+                let cell = (
+                    col.wrapping_add_signed(step * dx),
+                    row.wrapping_add_signed(step * dy),
+                );
+                if !cells.contains(&cell) {
+                    cells.push(cell);
                 }
             }
         }
-        true
+        cells
     }
 
-    fn is_winner(&self, player: Player) -> bool {
-        if self
-            .board
-            .iter()
-            .any(|row| row.iter().all(|&cell| cell == Cell::Taken(player)))
-        {
-            return true;
+    // Marks of the player next to the cell in one direction, the cell itself not counted
+    fn run_length(&self, col: usize, row: usize, dx: isize, dy: isize, player: Player) -> usize {
+        let mut length = 0;
+        let (mut col, mut row) = (col, row);
+        loop {
+            match (col.checked_add_signed(dx), row.checked_add_signed(dy)) {
+                (Some(next_col), Some(next_row))
+                    if next_col < self.width
+                        && next_row < self.height
+                        && self.cell(next_col, next_row) == Cell::Taken(player) =>
+                {
+                    length += 1;
+                    (col, row) = (next_col, next_row);
+                }
+                _ => return length,
+            }
         }
+    }
+}
 
-        if (0..BOARD_SIZE)
-            .any(|col| (0..BOARD_SIZE).all(|row| self.board[row][col] == Cell::Taken(player)))
-        {
-            return true;
+impl Display for TicTacToe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.board.chunks(self.width) {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
         }
+        Ok(())
+    }
+}
+
+#[derive(Parser)]
+#[command(
+    name = "tic_tac_toe",
+    about = "Tic-tac-toe for two players or against the computer",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Play against the computer at this level
+    #[arg(long, value_enum)]
+    ai: Option<Difficulty>,
+    /// Mark played by the computer, X moves first
+    #[arg(long, value_enum, ignore_case = true, default_value_t = Player::O)]
+    computer: Player,
+    #[command(flatten)]
+    board: BoardArgs,
+    /// Write the game record to this file after every move
+    #[arg(long)]
+    save: Option<PathBuf>,
+    /// Resume the game recorded in this file
+    #[arg(long, conflicts_with_all = ["width", "height", "win_length"])]
+    load: Option<PathBuf>,
+    /// Print the board after every move instead of the interactive board,
+    /// used anyway when the input or output is not a terminal
+    #[arg(long)]
+    plain: bool,
+}
 
-        if (0..BOARD_SIZE).all(|i| self.board[i][i] == Cell::Taken(player)) {
-            return true;
+#[derive(clap::Args)]
+struct BoardArgs {
+    /// Number of columns
+    #[arg(long, default_value_t = DEFAULT_SIZE, value_parser = parse_size)]
+    width: usize,
+    /// Number of rows
+    #[arg(long, default_value_t = DEFAULT_SIZE, value_parser = parse_size)]
+    height: usize,
+    /// Marks in a row needed to win, defaults to the shorter board side
+    #[arg(short = 'k', long, value_parser = parse_size)]
+    win_length: Option<usize>,
+}
+
+impl BoardArgs {
+    fn new_game(&self) -> Result<TicTacToe, String> {
+        let win_length = self.win_length.unwrap_or(self.width.min(self.height));
+        if win_length > self.width.max(self.height) {
+            return Err(format!(
+                "{win_length} in a row does not fit on a {}x{} board",
+                self.width, self.height
+            ));
         }
+        Ok(TicTacToe::new(self.width, self.height, win_length))
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Step through a recorded game move by move, Enter shows the next move
+    Replay { file: PathBuf },
+    /// Wait for a player to join over the network and play X against them
+    Host {
+        #[arg(short, long, default_value_t = DEFAULT_PORT)]
+        port: u16,
+        #[command(flatten)]
+        board: BoardArgs,
+    },
+    /// Join a hosted game at ADDRESS, e.g. 127.0.0.1:7000, and play O
+    Join { address: String },
+}
 
-        if (0..BOARD_SIZE).all(|i| self.board[i][BOARD_SIZE - 1 - i] == Cell::Taken(player)) {
-            return true;
+fn parse_size(value: &str) -> Result<usize, String> {
+    let size: usize = value
+        .parse()
+        .map_err(|_| format!("not a number: {value}"))?;
+    if !(1..=MAX_SIZE).contains(&size) {
+        return Err(format!("must be between 1 and {MAX_SIZE}"));
+    }
+    Ok(size)
+}
+
+fn new_game(cli: &Cli) -> Result<TicTacToe, String> {
+    if let Some(path) = &cli.load {
+        let game = load(path)?;
+        if game.is_over() {
+            return Err(format!(
+                "the game in {} has ended, use replay to watch it",
+                path.display()
+            ));
         }
-        false
+        return Ok(game);
     }
+    cli.board.new_game()
 }
 
-fn read_coordinates() -> Option<(usize, usize)> {
-    let mut input = String::new();
-    io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    let coordinates: Vec<usize> = input
-        .trim()
-        .split_whitespace()
-        .filter_map(|value| value.parse().ok())
-        .collect();
-    if coordinates.len() != 2 {
-        return None;
+/// Takes back the last move, against the computer also its reply so the player moves again
+fn undo_turn(game: &mut TicTacToe, against_computer: bool) -> bool {
+    let count = if against_computer { 2 } else { 1 };
+    if game.moves.len() < count {
+        return false;
+    }
+    for _ in 0..count {
+        game.undo();
     }
-    let col = coordinates[0] - 1;
-    let row = coordinates[1] - 1;
-    Some((col, row))
+    true
 }
 
-pub fn run() {
-    let mut game = TicTacToe::new();
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Outcome {
+    Won(Player),
+    Draw,
+    Quit,
+}
 
+/// Plays until the game ends, the player quits or the input ends
+fn game_loop<R: Rng>(
+    game: &mut TicTacToe,
+    computer: &mut Option<Ai<R>>,
+    save_to: Option<&Path>,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Outcome, String> {
     loop {
-        game.display();
-        println!("Player {} enter move. Enter col(1-3) row(1-3)", game.player);
+        let _ = write!(output, "{game}");
 
-        let Some((col, row)) = read_coordinates() else {
-            println!("Invalid coordinates");
-            continue;
+        let computer_move = computer
+            .as_mut()
+            .filter(|ai| ai.player == game.player)
+            .and_then(|ai| ai.choose_move(game));
+        let (col, row) = if let Some((col, row)) = computer_move {
+            let _ = writeln!(output, "Computer plays {}", cell_name(col, row));
+            (col, row)
+        } else {
+            let _ = writeln!(
+                output,
+                "Player {} enter move, e.g. b2 or 2 2 (help for commands)",
+                game.player
+            );
+            let mut line = String::new();
+            match input.read_line(&mut line) {
+                Ok(0) => return Ok(Outcome::Quit),
+                Ok(_) => {}
+                Err(error) => return Err(format!("Cannot read input: {error}")),
+            }
+            match parse_input(&line) {
+                Ok(Input::Move(col, row)) => (col, row),
+                Ok(Input::Undo) => {
+                    if !undo_turn(game, computer.is_some()) {
+                        let _ = writeln!(output, "Nothing to undo");
+                        continue;
+                    }
+                    if let Some(path) = save_to {
+                        save(path, game)?;
+                    }
+                    continue;
+                }
+                Ok(Input::Quit) => return Ok(Outcome::Quit),
+                Ok(Input::Help) => {
+                    let _ = writeln!(output, "{HELP}");
+                    continue;
+                }
+                Err(error) => {
+                    let _ = writeln!(output, "{error}");
+                    continue;
+                }
+            }
         };
 
-        if !game.make_move(col, row, game.player) {
-            println!("Invalid move");
+        if !game.play(col, row) {
+            let _ = writeln!(output, "Invalid move");
             continue;
         }
+        if let Some(path) = save_to {
+            save(path, game)?;
+        }
 
-        if game.is_winner(game.player) {
-            println!("Player {} wins", game.player);
-            break;
+        if let Some(winner) = game.winner() {
+            let _ = writeln!(output, "Player {winner} wins");
+            return Ok(Outcome::Won(winner));
         }
 
         if game.is_board_full() {
-            print!("The game has ended");
-            break;
+            let _ = writeln!(output, "The game has ended");
+            return Ok(Outcome::Draw);
+        }
+    }
+}
+
+fn play(cli: &Cli) -> Result<(), String> {
+    let mut computer = cli
+        .ai
+        .map(|difficulty| Ai::new(cli.computer, difficulty, rand::rng()));
+    let mut game = new_game(cli)?;
+    if !cli.plain && io::stdin().is_terminal() && io::stdout().is_terminal() {
+        return tui::play_rounds(game, &mut computer, cli.save.as_deref());
+    }
+    game_loop(
+        &mut game,
+        &mut computer,
+        cli.save.as_deref(),
+        &mut io::stdin().lock(),
+        &mut io::stdout(),
+    )?;
+    Ok(())
+}
+
+fn replay(path: &Path) -> Result<(), String> {
+    let recorded = load(path)?;
+    let mut game = TicTacToe::new(recorded.width, recorded.height, recorded.win_length);
+    print!("{game}");
+    for (number, &(col, row)) in recorded.moves.iter().enumerate() {
+        let mut input = String::new();
+        // At the end of the input the remaining moves are shown without waiting
+        let _ = io::stdin().read_line(&mut input);
+        println!("{}. {} {}", number + 1, game.player, cell_name(col, row));
+        game.play(col, row);
+        print!("{game}");
+    }
+    match game.winner() {
+        Some(winner) => println!("Player {winner} wins"),
+        None if game.is_board_full() => println!("The game has ended"),
+        None => println!("The game was not finished"),
+    }
+    Ok(())
+}
+
+fn host(port: u16, board: &BoardArgs) -> Result<(), String> {
+    let mut game = board.new_game()?;
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .map_err(|error| format!("Cannot listen on port {port}: {error}"))?;
+    println!("Waiting for a player on port {port}");
+    let (stream, address) = listener
+        .accept()
+        .map_err(|error| format!("Cannot accept a player: {error}"))?;
+    println!("Player O joined from {address}");
+    host_game(
+        &mut game,
+        &mut Connection::new(stream)?,
+        &mut io::stdin().lock(),
+        &mut io::stdout(),
+    )?;
+    Ok(())
+}
+
+fn join(address: &str) -> Result<(), String> {
+    let stream = TcpStream::connect(address)
+        .map_err(|error| format!("Cannot connect to {address}: {error}"))?;
+    println!("Joined the game at {address}, you play O");
+    join_game(
+        &mut Connection::new(stream)?,
+        &mut io::stdin().lock(),
+        &mut io::stdout(),
+    )?;
+    Ok(())
+}
+
+pub fn run() {
+    let cli = Cli::parse();
+    let result = match &cli.command {
+        Some(Command::Replay { file }) => replay(file),
+        Some(Command::Host { port, board }) => host(*port, board),
+        Some(Command::Join { address }) => join(address),
+        None => play(&cli),
+    };
+    if let Err(error) = result {
+        println!("Error: {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    fn game(width: usize, height: usize, win_length: usize, moves: &[(usize, usize)]) -> TicTacToe {
+        let mut game = TicTacToe::new(width, height, win_length);
+        for &(col, row) in moves {
+            assert!(game.make_move(col, row, Player::X));
+            game.moves.push((col, row));
         }
+        game
+    }
+
+    #[test]
+    fn detects_k_in_a_row_through_the_last_move() {
+        let row = game(7, 4, 4, &[(2, 1), (3, 1), (5, 1), (4, 1)]);
+        let column = game(7, 4, 4, &[(6, 0), (6, 1), (6, 3), (6, 2)]);
+        let diagonal = game(7, 4, 4, &[(1, 0), (2, 1), (4, 3), (3, 2)]);
+        let anti_diagonal = game(7, 4, 4, &[(6, 0), (5, 1), (3, 3), (4, 2)]);
+        let short = game(7, 4, 4, &[(0, 0), (1, 1), (2, 2)]);
+
+        assert!(row.is_winning_move(4, 1));
+        assert!(row.is_winning_move(2, 1));
+        assert!(column.is_winning_move(6, 2));
+        assert!(diagonal.is_winning_move(3, 2));
+        assert!(anti_diagonal.is_winning_move(4, 2));
+        assert!(!short.is_winning_move(2, 2));
+        assert!(!row.is_winning_move(0, 0));
+        assert_eq!(row.winning_line(), vec![(2, 1), (3, 1), (4, 1), (5, 1)]);
+        assert_eq!(anti_diagonal.winning_line().len(), 4);
+        assert!(short.winning_line().is_empty());
+    }
+
+    #[test]
+    fn counts_taken_cells_to_tell_a_full_board() {
+        let mut game = game(2, 1, 2, &[(0, 0)]);
+
+        assert!(!game.make_move(0, 0, Player::O));
+        assert!(!game.make_move(2, 0, Player::O));
+        assert!(game.make_move(1, 0, Player::O));
+        assert!(game.is_board_full());
+        game.clear(1, 0);
+        game.clear(1, 0);
+        assert_eq!(game.empty_cells(), vec![(1, 0)]);
+    }
+
+    fn play_input(
+        game: &mut TicTacToe,
+        computer: &mut Option<Ai<StdRng>>,
+        input: &str,
+    ) -> (Outcome, String) {
+        let mut output = Vec::new();
+        let outcome =
+            game_loop(game, computer, None, &mut Cursor::new(input), &mut output).unwrap();
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn plays_a_game_from_the_given_input() {
+        let mut game = TicTacToe::new(3, 3, 3);
+
+        let (outcome, output) = play_input(
+            &mut game,
+            &mut None,
+            "b2\na1\n0 1\nb2\nhelp\nc2\nb1\n2,1\na2\n",
+        );
+
+        assert_eq!(outcome, Outcome::Won(Player::X));
+        assert!(output.contains("Invalid coordinate: 0"));
+        assert!(output.contains("Invalid move"));
+        assert!(output.contains(HELP));
+        assert!(output.ends_with("Player X wins\n"));
+    }
+
+    #[test]
+    fn undoes_moves_and_stops_at_the_end_of_input() {
+        let mut game = TicTacToe::new(3, 3, 3);
+        let mut computer = Some(Ai::new(
+            Player::O,
+            Difficulty::Hard,
+            StdRng::seed_from_u64(44),
+        ));
+
+        let (outcome, output) = play_input(&mut game, &mut None, "b2\nundo\nundo\na1\n");
+        let (_, against_computer) =
+            play_input(&mut TicTacToe::new(3, 3, 3), &mut computer, "b2\nundo\n");
 
-        game.toggle_player();
+        assert_eq!(outcome, Outcome::Quit);
+        assert_eq!(game.moves, vec![(0, 0)]);
+        assert!(output.contains("Nothing to undo"));
+        assert_eq!(against_computer.matches("Computer plays").count(), 1);
+        assert!(
+            against_computer.ends_with(
+                "---\n---\n---\nPlayer X enter move, e.g. b2 or 2 2 (help for commands)\n"
+            )
+        );
     }
 }
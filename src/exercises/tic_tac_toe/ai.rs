@@ -0,0 +1,288 @@
+use rand::{Rng, RngExt};
+
+use super::{Cell, Player, TicTacToe};
+
+const WIN_SCORE: i32 = 1_000;
+/// Empty cells up to which the game is searched to the end
+const EXACT_SEARCH_CELLS: usize = 9;
+/// Moves looked ahead on larger boards
+const LOOKAHEAD: i32 = 3;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// Chance in percent of playing a random move instead of the best one
+    fn random_move_percent(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 60,
+            Difficulty::Medium => 25,
+            Difficulty::Hard => 0,
+        }
+    }
+}
+
+/// Computer player, the random source decides when easier levels blunder
+pub struct Ai<R> {
+    pub player: Player,
+    difficulty: Difficulty,
+    rng: R,
+}
+
+impl<R: Rng> Ai<R> {
+    pub fn new(player: Player, difficulty: Difficulty, rng: R) -> Self {
+        Ai {
+            player,
+            difficulty,
+            rng,
+        }
+    }
+
+    pub fn choose_move(&mut self, game: &TicTacToe) -> Option<(usize, usize)> {
+        let moves = game.empty_cells();
+        if moves.is_empty() {
+            return None;
+        }
+        let percent = self.difficulty.random_move_percent();
+        if percent > 0 && self.rng.random_range(0..100) < percent {
+            return Some(moves[self.rng.random_range(0..moves.len())]);
+        }
+        best_move(game, self.player)
+    }
+}
+
+/// Move with the best minimax score for the player, the first one on ties
+pub fn best_move(game: &TicTacToe, player: Player) -> Option<(usize, usize)> {
+    let lookahead = (game.empty_cells().len() > EXACT_SEARCH_CELLS).then_some(LOOKAHEAD);
+    let mut board = game.clone();
+    let mut best = None;
+    let mut alpha = -WIN_SCORE;
+    for (col, row) in candidates(game, lookahead) {
+        let score = score_move(
+            &mut board,
+            (col, row),
+            player,
+            1,
+            lookahead,
+            alpha,
+            WIN_SCORE,
+        );
+        if best.is_none() || score > alpha {
+            alpha = score;
+            best = Some((col, row));
+        }
+    }
+    best
+}
+
+// All empty cells when searching to the end, otherwise only the ones next to a mark
+// since a move far away from the others cannot change the next few moves.
+fn candidates(board: &TicTacToe, lookahead: Option<i32>) -> Vec<(usize, usize)> {
+    let moves = board.empty_cells();
+    if lookahead.is_none() {
+        return moves;
+    }
+    if board.taken == 0 {
+        return vec![(board.width / 2, board.height / 2)];
+    }
+    moves
+        .into_iter()
+        .filter(|&(col, row)| {
+            (row.saturating_sub(1)..=(row + 1).min(board.height - 1)).any(|near_row| {
+                (col.saturating_sub(1)..=(col + 1).min(board.width - 1))
+                    .any(|near_col| board.cell(near_col, near_row) != Cell::Empty)
+            })
+        })
+        .collect()
+}
+
+// Plays the move, scores it for the player and takes it back. Scores come from
+// minimax in negamax form with alpha-beta pruning: faster wins and slower losses score
+// better so the AI does not play for time, positions past the lookahead score as a draw.
+fn score_move(
+    board: &mut TicTacToe,
+    (col, row): (usize, usize),
+    player: Player,
+    depth: i32,
+    lookahead: Option<i32>,
+    alpha: i32,
+    beta: i32,
+) -> i32 {
+    board.make_move(col, row, player);
+    let score = if board.is_winning_move(col, row) {
+        WIN_SCORE - depth
+    } else if board.is_board_full() || lookahead == Some(depth) {
+        0
+    } else {
+        -minimax(
+            board,
+            player.opponent(),
+            depth + 1,
+            lookahead,
+            -beta,
+            -alpha,
+        )
+    };
+    board.clear(col, row);
+    score
+}
+
+fn minimax(
+    board: &mut TicTacToe,
+    player: Player,
+    depth: i32,
+    lookahead: Option<i32>,
+    mut alpha: i32,
+    beta: i32,
+) -> i32 {
+    let mut best = -WIN_SCORE;
+    for position in candidates(board, lookahead) {
+        let score = score_move(board, position, player, depth, lookahead, alpha, beta);
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    fn play(game: &TicTacToe, col: usize, row: usize) -> TicTacToe {
+        let mut next = game.clone();
+        assert!(next.play(col, row));
+        next
+    }
+
+    // Plays every possible opponent reply, returns the number of finished games
+    fn assert_never_loses(game: &TicTacToe, ai: Player) -> usize {
+        if let Some(player) = game.winner() {
+            assert_eq!(player, ai, "AI lost");
+            return 1;
+        }
+        if game.is_board_full() {
+            return 1;
+        }
+        if game.player == ai {
+            let (col, row) = best_move(game, ai).unwrap();
+            return assert_never_loses(&play(game, col, row), ai);
+        }
+        game.empty_cells()
+            .into_iter()
+            .map(|(col, row)| assert_never_loses(&play(game, col, row), ai))
+            .sum()
+    }
+
+    fn key(game: &TicTacToe) -> String {
+        format!("{:?}", game.board)
+    }
+
+    // Game value for the player to move with perfect play on both sides: 1 win, 0 draw, -1 loss
+    fn value(game: &TicTacToe, values: &mut HashMap<String, i32>) -> i32 {
+        if let Some(value) = values.get(&key(game)) {
+            return *value;
+        }
+        let value = if game.winner().is_some() {
+            -1
+        } else if game.is_board_full() {
+            0
+        } else {
+            game.empty_cells()
+                .into_iter()
+                .map(|(col, row)| -value(&play(game, col, row), values))
+                .max()
+                .unwrap()
+        };
+        values.insert(key(game), value);
+        value
+    }
+
+    fn reachable(game: &TicTacToe, positions: &mut HashMap<String, TicTacToe>) {
+        if game.winner().is_some() || game.is_board_full() || positions.contains_key(&key(game)) {
+            return;
+        }
+        positions.insert(key(game), game.clone());
+        for (col, row) in game.empty_cells() {
+            reachable(&play(game, col, row), positions);
+        }
+    }
+
+    #[test]
+    fn hard_ai_never_loses_against_any_replies() {
+        let game = TicTacToe::new(3, 3, 3);
+
+        assert!(assert_never_loses(&game, Player::X) > 0);
+        assert!(assert_never_loses(&game, Player::O) > 0);
+    }
+
+    #[test]
+    fn hard_ai_keeps_the_game_value_from_every_reachable_position() {
+        let mut positions = HashMap::new();
+        reachable(&TicTacToe::new(3, 3, 3), &mut positions);
+        let mut values = HashMap::new();
+
+        assert_eq!(positions.len(), 4_520);
+        for game in positions.values() {
+            let (col, row) = best_move(game, game.player).unwrap();
+
+            assert_eq!(
+                -value(&play(game, col, row), &mut values),
+                value(game, &mut values),
+                "{}",
+                key(game)
+            );
+        }
+    }
+
+    #[test]
+    fn hard_ai_wins_when_it_can() {
+        let mut game = TicTacToe::new(3, 3, 3);
+        for (col, row) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            game = play(&game, col, row);
+        }
+
+        assert_eq!(best_move(&game, Player::X), Some((2, 0)));
+    }
+
+    #[test]
+    fn looks_ahead_on_large_boards() {
+        let mut game = TicTacToe::new(15, 15, 5);
+        for col in 5..9 {
+            game.make_move(col, 7, Player::X);
+        }
+        for (col, row) in [(4, 7), (0, 0), (0, 2), (0, 4)] {
+            game.make_move(col, row, Player::O);
+        }
+
+        assert_eq!(best_move(&game, Player::X), Some((9, 7)));
+        assert_eq!(best_move(&game, Player::O), Some((9, 7)));
+    }
+
+    #[test]
+    fn easier_levels_sometimes_play_random_moves() {
+        let mut game = TicTacToe::new(3, 3, 3);
+        for (col, row) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            game = play(&game, col, row);
+        }
+        let mut easy = Ai::new(Player::X, Difficulty::Easy, StdRng::seed_from_u64(41));
+        let mut hard = Ai::new(Player::X, Difficulty::Hard, StdRng::seed_from_u64(41));
+
+        let easy_moves: Vec<_> = (0..50).map(|_| easy.choose_move(&game).unwrap()).collect();
+
+        assert!(easy_moves.iter().any(|chosen| *chosen != (2, 0)));
+        assert!(easy_moves.contains(&(2, 0)));
+        assert!((0..50).all(|_| hard.choose_move(&game) == Some((2, 0))));
+    }
+}
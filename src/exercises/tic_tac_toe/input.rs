@@ -0,0 +1,65 @@
+use super::notation::parse_cell;
+
+pub const HELP: &str = "\
+Moves: a cell name such as b2 (column letter, row number) or col row such as 2 2
+Commands:
+  undo  take back the last move, against the computer also its reply
+  quit  end the game
+  help  show this help";
+
+#[derive(Debug, PartialEq)]
+pub enum Input {
+    Move(usize, usize),
+    Undo,
+    Quit,
+    Help,
+}
+
+/// Reads a command or a move, coordinates are 1-based, the board checks the range
+pub fn parse_input(line: &str) -> Result<Input, String> {
+    let line = line.trim();
+    match line.to_ascii_lowercase().as_str() {
+        "undo" | "u" => return Ok(Input::Undo),
+        "quit" | "q" | "exit" => return Ok(Input::Quit),
+        "help" | "h" | "?" => return Ok(Input::Help),
+        _ => {}
+    }
+    if let Some((col, row)) = parse_cell(line) {
+        return Ok(Input::Move(col, row));
+    }
+
+    let numbers: Vec<&str> = line
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|value| !value.is_empty())
+        .collect();
+    let [col, row] = numbers[..] else {
+        return Err(format!("Invalid coordinates: {line}, type help for help"));
+    };
+    let coordinate = |value: &str| {
+        value
+            .parse::<usize>()
+            .ok()
+            .and_then(|value| value.checked_sub(1))
+            .ok_or_else(|| format!("Invalid coordinate: {value}, counting starts at 1"))
+    };
+    Ok(Input::Move(coordinate(col)?, coordinate(row)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_moves_and_commands() {
+        assert_eq!(parse_input("b2\n"), Ok(Input::Move(1, 1)));
+        assert_eq!(parse_input(" 3 1 "), Ok(Input::Move(2, 0)));
+        assert_eq!(parse_input("3,1"), Ok(Input::Move(2, 0)));
+        assert_eq!(parse_input("UNDO"), Ok(Input::Undo));
+        assert_eq!(parse_input("q"), Ok(Input::Quit));
+        assert_eq!(parse_input("help"), Ok(Input::Help));
+        assert!(parse_input("0 1").is_err());
+        assert!(parse_input("1 -1").is_err());
+        assert!(parse_input("1 2 3").is_err());
+        assert!(parse_input("").is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! Two players over TCP. The host plays X and keeps the game, the client plays O and
+//! only shows what the host sends. Each message is one line:
+//!
+//! - `MOVE b2` client move
+//! - `BOARD O X--/-O-/---` player to move, `-` once the game is over, and the board rows,
+//!   sent after every move
+//! - `ERROR text` the client move was rejected, the client moves again
+//! - `RESULT X`, `RESULT O`, `RESULT DRAW` or `RESULT QUIT` the game is over
+
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+use clap::ValueEnum;
+
+use super::input::{HELP, Input, parse_input};
+use super::notation::{cell_name, parse_cell};
+use super::{Outcome, Player, TicTacToe};
+
+const HOST_PLAYER: Player = Player::X;
+const CLIENT_PLAYER: Player = Player::O;
+
+#[derive(Debug, PartialEq)]
+pub enum Message {
+    Move(usize, usize),
+    Board {
+        to_move: Option<Player>,
+        rows: String,
+    },
+    Error(String),
+    Result(Outcome),
+}
+
+impl Message {
+    fn board(game: &TicTacToe) -> Message {
+        Message::Board {
+            to_move: (!game.is_over()).then_some(game.player),
+            rows: game.to_string().trim_end().replace('\n', "/"),
+        }
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Message::Move(col, row) => write!(f, "MOVE {}", cell_name(*col, *row)),
+            Message::Board { to_move, rows } => match to_move {
+                Some(player) => write!(f, "BOARD {player} {rows}"),
+                None => write!(f, "BOARD - {rows}"),
+            },
+            Message::Error(text) => write!(f, "ERROR {text}"),
+            Message::Result(Outcome::Won(player)) => write!(f, "RESULT {player}"),
+            Message::Result(Outcome::Draw) => write!(f, "RESULT DRAW"),
+            Message::Result(Outcome::Quit) => write!(f, "RESULT QUIT"),
+        }
+    }
+}
+
+pub fn parse_message(line: &str) -> Result<Message, String> {
+    let line = line.trim();
+    let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+    match kind {
+        "MOVE" => parse_cell(rest)
+            .map(|(col, row)| Message::Move(col, row))
+            .ok_or_else(|| format!("invalid cell: {rest}")),
+        "BOARD" => {
+            let (to_move, rows) = rest
+                .split_once(' ')
+                .ok_or_else(|| format!("invalid board: {rest}"))?;
+            Ok(Message::Board {
+                to_move: match to_move {
+                    "-" => None,
+                    player => Some(Player::from_str(player, true)?),
+                },
+                rows: rows.to_string(),
+            })
+        }
+        "ERROR" => Ok(Message::Error(rest.to_string())),
+        "RESULT" => match rest {
+            "DRAW" => Ok(Message::Result(Outcome::Draw)),
+            "QUIT" => Ok(Message::Result(Outcome::Quit)),
+            player => Ok(Message::Result(Outcome::Won(Player::from_str(
+                player, true,
+            )?))),
+        },
+        _ => Err(format!("unknown message: {line}")),
+    }
+}
+
+pub struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    pub fn new(stream: TcpStream) -> Result<Connection, String> {
+        let reader = stream
+            .try_clone()
+            .map_err(|error| format!("Cannot use the connection: {error}"))?;
+        Ok(Connection {
+            reader: BufReader::new(reader),
+            writer: stream,
+        })
+    }
+
+    pub fn send(&mut self, message: &Message) -> Result<(), String> {
+        writeln!(self.writer, "{message}").map_err(|error| format!("Cannot send: {error}"))
+    }
+
+    /// Next line from the other side, `None` once the connection is closed
+    fn receive_line(&mut self) -> Result<Option<String>, String> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(line)),
+            Err(error) => Err(format!("Cannot receive: {error}")),
+        }
+    }
+}
+
+/// Asks the local player for a move until a valid looking one is entered, `None` on quit
+fn read_move(
+    player: Player,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Option<(usize, usize)>, String> {
+    loop {
+        let _ = writeln!(
+            output,
+            "Player {player} enter move, e.g. b2 (help for commands)"
+        );
+        let mut line = String::new();
+        match input.read_line(&mut line) {
+            Ok(0) => return Ok(None),
+            Ok(_) => {}
+            Err(error) => return Err(format!("Cannot read input: {error}")),
+        }
+        match parse_input(&line) {
+            Ok(Input::Move(col, row)) => return Ok(Some((col, row))),
+            Ok(Input::Quit) => return Ok(None),
+            Ok(Input::Undo) => {
+                let _ = writeln!(output, "Undo is not available in network games");
+            }
+            Ok(Input::Help) => {
+                let _ = writeln!(output, "{HELP}");
+            }
+            Err(error) => {
+                let _ = writeln!(output, "{error}");
+            }
+        }
+    }
+}
+
+fn announce(outcome: &Outcome, output: &mut impl Write) {
+    let _ = match outcome {
+        Outcome::Won(player) => writeln!(output, "Player {player} wins"),
+        Outcome::Draw => writeln!(output, "The game has ended"),
+        Outcome::Quit => writeln!(output, "The game was abandoned"),
+    };
+}
+
+/// Plays X against the connected client, every client move is checked here
+pub fn host_game(
+    game: &mut TicTacToe,
+    connection: &mut Connection,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Outcome, String> {
+    connection.send(&Message::board(game))?;
+    loop {
+        let _ = write!(output, "{game}");
+        let (col, row) = if game.player == HOST_PLAYER {
+            let Some((col, row)) = read_move(HOST_PLAYER, input, output)? else {
+                connection.send(&Message::Result(Outcome::Quit))?;
+                announce(&Outcome::Quit, output);
+                return Ok(Outcome::Quit);
+            };
+            if !game.is_valid_move(col, row) {
+                let _ = writeln!(output, "Invalid move");
+                continue;
+            }
+            (col, row)
+        } else {
+            let _ = writeln!(output, "Waiting for player {CLIENT_PLAYER}");
+            let Some(line) = connection.receive_line()? else {
+                announce(&Outcome::Quit, output);
+                return Ok(Outcome::Quit);
+            };
+            match parse_message(&line) {
+                Ok(Message::Move(col, row)) if game.is_valid_move(col, row) => {
+                    let _ = writeln!(
+                        output,
+                        "Player {CLIENT_PLAYER} plays {}",
+                        cell_name(col, row)
+                    );
+                    (col, row)
+                }
+                Ok(Message::Move(col, row)) => {
+                    connection.send(&Message::Error(format!(
+                        "{} is not a free cell",
+                        cell_name(col, row)
+                    )))?;
+                    continue;
+                }
+                Ok(_) => {
+                    connection.send(&Message::Error(String::from("expected MOVE")))?;
+                    continue;
+                }
+                Err(error) => {
+                    connection.send(&Message::Error(error))?;
+                    continue;
+                }
+            }
+        };
+
+        game.play(col, row);
+        connection.send(&Message::board(game))?;
+        let Some(outcome) = game.outcome() else {
+            continue;
+        };
+        let _ = write!(output, "{game}");
+        connection.send(&Message::Result(outcome))?;
+        announce(&outcome, output);
+        return Ok(outcome);
+    }
+}
+
+/// Plays O on the host's board, moves are sent when the host says it is O's turn
+pub fn join_game(
+    connection: &mut Connection,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Outcome, String> {
+    loop {
+        let line = connection
+            .receive_line()?
+            .ok_or("the host closed the connection")?;
+        let your_turn = match parse_message(&line)? {
+            Message::Board { to_move, rows } => {
+                let _ = writeln!(output, "{}", rows.replace('/', "\n"));
+                if let Some(player) = to_move.filter(|player| *player != CLIENT_PLAYER) {
+                    let _ = writeln!(output, "Waiting for player {player}");
+                }
+                to_move == Some(CLIENT_PLAYER)
+            }
+            Message::Error(text) => {
+                let _ = writeln!(output, "{text}");
+                true
+            }
+            Message::Result(outcome) => {
+                announce(&outcome, output);
+                return Ok(outcome);
+            }
+            Message::Move(..) => return Err(format!("unexpected message: {}", line.trim())),
+        };
+        if your_turn {
+            let Some((col, row)) = read_move(CLIENT_PLAYER, input, output)? else {
+                announce(&Outcome::Quit, output);
+                return Ok(Outcome::Quit);
+            };
+            connection.send(&Message::Move(col, row))?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    fn connect_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (host, _) = listener.accept().unwrap();
+        for stream in [&host, &client] {
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+        }
+        (host, client)
+    }
+
+    fn spawn_host(
+        stream: TcpStream,
+        input: &'static str,
+    ) -> thread::JoinHandle<(Result<Outcome, String>, String)> {
+        thread::spawn(move || {
+            let mut game = TicTacToe::new(3, 3, 3);
+            let mut output = Vec::new();
+            let outcome = host_game(
+                &mut game,
+                &mut Connection::new(stream).unwrap(),
+                &mut Cursor::new(input),
+                &mut output,
+            );
+            (outcome, String::from_utf8(output).unwrap())
+        })
+    }
+
+    #[test]
+    fn reads_and_writes_protocol_lines() {
+        let messages = [
+            Message::Move(1, 1),
+            Message::Board {
+                to_move: Some(Player::O),
+                rows: String::from("X--/-O-/---"),
+            },
+            Message::Board {
+                to_move: None,
+                rows: String::from("XXX/OO-/---"),
+            },
+            Message::Error(String::from("b2 is not a free cell")),
+            Message::Result(Outcome::Won(Player::X)),
+            Message::Result(Outcome::Draw),
+            Message::Result(Outcome::Quit),
+        ];
+
+        for message in messages {
+            assert_eq!(parse_message(&format!("{message}\n")), Ok(message));
+        }
+        assert!(parse_message("MOVE 2 2").is_err());
+        assert!(parse_message("HELLO").is_err());
+    }
+
+    #[test]
+    fn plays_a_game_between_host_and_client_on_localhost() {
+        let (host, client) = connect_pair();
+        let host = spawn_host(host, "b2\nc2\na2\n");
+
+        let mut output = Vec::new();
+        let client_outcome = join_game(
+            &mut Connection::new(client).unwrap(),
+            &mut Cursor::new("a1\nb2\nb1\n"),
+            &mut output,
+        );
+        let (host_outcome, host_output) = host.join().unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(host_outcome, Ok(Outcome::Won(Player::X)));
+        assert_eq!(client_outcome, Ok(Outcome::Won(Player::X)));
+        assert!(host_output.contains("Player O plays a1"));
+        assert!(output.contains("b2 is not a free cell"));
+        assert!(output.ends_with("OO-\nXXX\n---\nPlayer X wins\n"));
+    }
+
+    #[test]
+    fn host_rejects_invalid_messages() {
+        let (host, client) = connect_pair();
+        let host = spawn_host(host, "b2\n");
+        let mut connection = Connection::new(client).unwrap();
+        let mut receive = || {
+            let line = connection.receive_line().unwrap().unwrap();
+            parse_message(&line).unwrap()
+        };
+
+        assert!(matches!(
+            receive(),
+            Message::Board {
+                to_move: Some(Player::X),
+                ..
+            }
+        ));
+        assert!(matches!(
+            receive(),
+            Message::Board {
+                to_move: Some(Player::O),
+                ..
+            }
+        ));
+        for line in ["HELLO\n", "BOARD X ---\n", "MOVE b2\n", "MOVE d1\n"] {
+            connection.writer.write_all(line.as_bytes()).unwrap();
+            assert!(matches!(
+                parse_message(&connection.receive_line().unwrap().unwrap()),
+                Ok(Message::Error(_))
+            ));
+        }
+        drop(connection);
+
+        assert_eq!(host.join().unwrap().0, Ok(Outcome::Quit));
+    }
+}
@@ -0,0 +1,132 @@
+use std::fs;
+use std::path::Path;
+
+use super::{MAX_SIZE, TicTacToe};
+
+/// Cell name with a column letter and a row number counted from the top, `(1, 1)` is `b2`
+pub fn cell_name(col: usize, row: usize) -> String {
+    format!("{}{}", char::from(b'a' + col as u8), row + 1)
+}
+
+pub fn parse_cell(name: &str) -> Option<(usize, usize)> {
+    let mut chars = name.chars();
+    let letter = chars.next()?.to_ascii_lowercase();
+    if !letter.is_ascii_lowercase() {
+        return None;
+    }
+    let row: usize = chars.as_str().parse().ok()?;
+    if row == 0 {
+        return None;
+    }
+    Some(((letter as u8 - b'a') as usize, row - 1))
+}
+
+/// Game record: a `WIDTHxHEIGHT k=LENGTH` header followed by numbered moves, X first
+///
+/// ```text
+/// 3x3 k=3
+/// 1. b2 a1
+/// 2. c3
+/// ```
+pub fn to_text(game: &TicTacToe) -> String {
+    let mut text = format!("{}x{} k={}\n", game.width, game.height, game.win_length);
+    for (number, pair) in game.moves.chunks(2).enumerate() {
+        let names: Vec<String> = pair.iter().map(|&(col, row)| cell_name(col, row)).collect();
+        text.push_str(&format!("{}. {}\n", number + 1, names.join(" ")));
+    }
+    text
+}
+
+/// Plays the recorded moves on a new board, rejecting illegal moves and moves after the end
+pub fn from_text(text: &str) -> Result<TicTacToe, String> {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    let header = lines.next().ok_or("empty game record")?;
+    let (width, height, win_length) =
+        parse_header(header).ok_or_else(|| format!("invalid header: {header}"))?;
+
+    let mut game = TicTacToe::new(width, height, win_length);
+    let names = lines
+        .flat_map(str::split_whitespace)
+        .filter(|token| !token.ends_with('.'));
+    for (number, name) in names.enumerate() {
+        if game.is_over() {
+            return Err(format!(
+                "move {} ({name}) after the end of the game",
+                number + 1
+            ));
+        }
+        let (col, row) =
+            parse_cell(name).ok_or_else(|| format!("move {}: invalid cell {name}", number + 1))?;
+        if !game.play(col, row) {
+            return Err(format!("move {} ({name}) is not allowed", number + 1));
+        }
+    }
+    Ok(game)
+}
+
+fn parse_header(header: &str) -> Option<(usize, usize, usize)> {
+    let (size, win_length) = header.split_once(" k=")?;
+    let (width, height) = size.split_once('x')?;
+    let [width, height, win_length] =
+        [width, height, win_length].map(|value| value.trim().parse::<usize>().ok());
+    let (width, height, win_length) = (width?, height?, win_length?);
+    let fits = |size| (1..=MAX_SIZE).contains(&size);
+    (fits(width) && fits(height) && win_length >= 1 && win_length <= width.max(height))
+        .then_some((width, height, win_length))
+}
+
+pub fn save(path: &Path, game: &TicTacToe) -> Result<(), String> {
+    fs::write(path, to_text(game))
+        .map_err(|error| format!("Cannot write {}: {error}", path.display()))
+}
+
+pub fn load(path: &Path) -> Result<TicTacToe, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|error| format!("Cannot read {}: {error}", path.display()))?;
+    from_text(&text).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_cells_by_column_letter_and_row_number() {
+        assert_eq!(cell_name(1, 1), "b2");
+        assert_eq!(cell_name(14, 14), "o15");
+        assert_eq!(parse_cell("b2"), Some((1, 1)));
+        assert_eq!(parse_cell("O15"), Some((14, 14)));
+        assert_eq!(parse_cell("a0"), None);
+        assert_eq!(parse_cell("2b"), None);
+        assert_eq!(parse_cell("b"), None);
+    }
+
+    #[test]
+    fn records_and_replays_games() {
+        let mut game = TicTacToe::new(4, 3, 3);
+        for (col, row) in [(1, 1), (0, 0), (2, 2)] {
+            assert!(game.play(col, row));
+        }
+
+        let text = to_text(&game);
+        let loaded = from_text(&text).unwrap();
+
+        assert_eq!(text, "4x3 k=3\n1. b2 a1\n2. c3\n");
+        assert_eq!(loaded.moves, game.moves);
+        assert_eq!(loaded.board, game.board);
+        assert_eq!(loaded.player, game.player);
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        assert!(from_text("").is_err());
+        assert!(from_text("3x3 k=4\n").is_err());
+        assert!(from_text("3x3 k=3\n1. b2 b2\n").is_err());
+        assert!(from_text("3x3 k=3\n1. d1\n").is_err());
+        assert!(from_text("3x3 k=3\n1. a1 b1 2. a2 b2 3. a3 b3\n").is_err());
+        assert!(from_text("# finished\n3x3 k=3\n1. a1 b1 2. a2 b2 3. a3\n").is_ok());
+    }
+}
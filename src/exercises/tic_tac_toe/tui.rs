@@ -0,0 +1,334 @@
+use std::fmt::Display;
+use std::io;
+use std::path::Path;
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::style::{Color, ContentStyle, Print, Stylize};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use rand::Rng;
+
+use super::ai::Ai;
+use super::notation::{cell_name, save};
+use super::{Cell, Outcome, Player, TicTacToe, undo_turn};
+
+const KEYS: &str = "arrows move, Enter places, u undo, n new round, q quit";
+
+/// Rounds won by each player and drawn, kept until the program ends
+#[derive(Debug, Default, PartialEq)]
+struct Score {
+    x_wins: u32,
+    o_wins: u32,
+    draws: u32,
+}
+
+impl Score {
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Won(Player::X) => self.x_wins += 1,
+            Outcome::Won(Player::O) => self.o_wins += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Quit => {}
+        }
+    }
+}
+
+impl Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "X {} : {} O, draws {}",
+            self.x_wins, self.o_wins, self.draws
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Cursor(isize, isize),
+    Place,
+    Undo,
+    NewRound,
+    Quit,
+}
+
+fn action(key: KeyEvent) -> Option<Action> {
+    // Some terminals report key releases too
+    if key.kind != KeyEventKind::Press {
+        return None;
+    }
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Left | KeyCode::Char('h') => Some(Action::Cursor(-1, 0)),
+        KeyCode::Right | KeyCode::Char('l') => Some(Action::Cursor(1, 0)),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::Cursor(0, -1)),
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::Cursor(0, 1)),
+        KeyCode::Enter | KeyCode::Char(' ') => Some(Action::Place),
+        KeyCode::Char('u') => Some(Action::Undo),
+        KeyCode::Char('n') => Some(Action::NewRound),
+        KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+/// Cursor moved by one cell, it stops at the edges of the board
+fn move_cursor(
+    (col, row): (usize, usize),
+    (dx, dy): (isize, isize),
+    game: &TicTacToe,
+) -> (usize, usize) {
+    (
+        col.saturating_add_signed(dx).min(game.width - 1),
+        row.saturating_add_signed(dy).min(game.height - 1),
+    )
+}
+
+fn color(player: Player) -> Color {
+    match player {
+        Player::X => Color::Cyan,
+        Player::O => Color::Magenta,
+    }
+}
+
+/// Whole screen in raw mode, so lines end with `\r\n`
+fn render(game: &TicTacToe, cursor: Option<(usize, usize)>, score: &Score, status: &str) -> String {
+    let winning_line = game.winning_line();
+    let mut screen = String::from("   ");
+    for col in 0..game.width {
+        screen.push_str(&format!(" {} ", char::from(b'a' + col as u8)));
+    }
+    screen.push_str("\r\n");
+    for row in 0..game.height {
+        screen.push_str(&format!("{:>2} ", row + 1));
+        for col in 0..game.width {
+            let (text, mut style) = match game.cell(col, row) {
+                Cell::Empty => (String::from(" · "), ContentStyle::new().dark_grey()),
+                Cell::Taken(player) => (
+                    format!(" {player} "),
+                    ContentStyle::new().with(color(player)).bold(),
+                ),
+            };
+            if winning_line.contains(&(col, row)) {
+                style = style.on(Color::DarkGreen);
+            }
+            if cursor == Some((col, row)) {
+                style = style.reverse();
+            }
+            screen.push_str(&style.apply(text).to_string());
+        }
+        screen.push_str("\r\n");
+    }
+    screen.push_str(&format!("\r\n{score}\r\n{status}\r\n{KEYS}\r\n"));
+    screen
+}
+
+fn terminal_error(error: io::Error) -> String {
+    format!("Terminal error: {error}")
+}
+
+/// Raw mode on the alternate screen, the terminal is restored when dropped
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(Screen)
+    }
+
+    fn draw(&self, screen: &str) -> Result<(), String> {
+        execute!(
+            io::stdout(),
+            MoveTo(0, 0),
+            Clear(ClearType::All),
+            Print(screen)
+        )
+        .map_err(terminal_error)
+    }
+
+    fn next_action(&self) -> Result<Action, String> {
+        loop {
+            if let Event::Key(key) = event::read().map_err(terminal_error)?
+                && let Some(action) = action(key)
+            {
+                return Ok(action);
+            }
+        }
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// How a round ended, only finished rounds count towards the score
+#[derive(Debug)]
+enum RoundEnd {
+    Finished(Outcome),
+    Restarted,
+    Quit,
+}
+
+/// Plays one round until it ends, the player restarts it or quits
+fn round<R: Rng>(
+    screen: &Screen,
+    game: &mut TicTacToe,
+    computer: &mut Option<Ai<R>>,
+    save_to: Option<&Path>,
+    score: &Score,
+) -> Result<RoundEnd, String> {
+    let mut cursor = game
+        .moves
+        .last()
+        .copied()
+        .unwrap_or((game.width / 2, game.height / 2));
+    let mut message = String::new();
+    loop {
+        if let Some(outcome) = game.outcome() {
+            return Ok(RoundEnd::Finished(outcome));
+        }
+        if let Some(ai) = computer.as_mut().filter(|ai| ai.player == game.player) {
+            screen.draw(&render(game, None, score, "Computer is thinking"))?;
+            if let Some((col, row)) = ai.choose_move(game) {
+                game.play(col, row);
+                cursor = (col, row);
+                message = format!("Computer plays {}", cell_name(col, row));
+            }
+        } else {
+            let status = format!("Player {} to move. {message}", game.player);
+            screen.draw(&render(game, Some(cursor), score, &status))?;
+            message.clear();
+            match screen.next_action()? {
+                Action::Cursor(dx, dy) => {
+                    cursor = move_cursor(cursor, (dx, dy), game);
+                    continue;
+                }
+                Action::Place => {
+                    if !game.play(cursor.0, cursor.1) {
+                        message = String::from("That cell is taken");
+                        continue;
+                    }
+                }
+                Action::Undo => {
+                    if !undo_turn(game, computer.is_some()) {
+                        message = String::from("Nothing to undo");
+                        continue;
+                    }
+                }
+                Action::NewRound => return Ok(RoundEnd::Restarted),
+                Action::Quit => return Ok(RoundEnd::Quit),
+            }
+        }
+        if let Some(path) = save_to {
+            save(path, game)?;
+        }
+    }
+}
+
+/// Interactive board in the terminal, rounds on the same board follow until the player quits
+pub fn play_rounds<R: Rng>(
+    first: TicTacToe,
+    computer: &mut Option<Ai<R>>,
+    save_to: Option<&Path>,
+) -> Result<(), String> {
+    let mut score = Score::default();
+    let mut game = first;
+    let screen = Screen::enter().map_err(terminal_error)?;
+    loop {
+        let outcome = match round(&screen, &mut game, computer, save_to, &score)? {
+            RoundEnd::Finished(outcome) => outcome,
+            RoundEnd::Restarted => {
+                game = TicTacToe::new(game.width, game.height, game.win_length);
+                continue;
+            }
+            RoundEnd::Quit => break,
+        };
+        score.record(outcome);
+        let status = match outcome {
+            Outcome::Won(player) => format!("Player {player} wins"),
+            _ => String::from("The game has ended"),
+        };
+        screen.draw(&render(&game, None, &score, &status))?;
+        loop {
+            match screen.next_action()? {
+                Action::NewRound => break,
+                Action::Quit => {
+                    drop(screen);
+                    println!("Score: {score}");
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        game = TicTacToe::new(game.width, game.height, game.win_length);
+    }
+    drop(screen);
+    println!("Score: {score}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn maps_keys_to_actions() {
+        assert_eq!(action(key(KeyCode::Left)), Some(Action::Cursor(-1, 0)));
+        assert_eq!(action(key(KeyCode::Char('j'))), Some(Action::Cursor(0, 1)));
+        assert_eq!(action(key(KeyCode::Enter)), Some(Action::Place));
+        assert_eq!(action(key(KeyCode::Char('n'))), Some(Action::NewRound));
+        assert_eq!(
+            action(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+        assert_eq!(action(key(KeyCode::Char('x'))), None);
+    }
+
+    #[test]
+    fn cursor_stops_at_the_edges() {
+        let game = TicTacToe::new(4, 3, 3);
+
+        assert_eq!(move_cursor((0, 0), (-1, 0), &game), (0, 0));
+        assert_eq!(move_cursor((3, 1), (1, 0), &game), (3, 1));
+        assert_eq!(move_cursor((3, 1), (0, 1), &game), (3, 2));
+        assert_eq!(move_cursor((3, 2), (0, 1), &game), (3, 2));
+    }
+
+    #[test]
+    fn highlights_the_winning_line_and_the_cursor() {
+        let mut game = TicTacToe::new(3, 3, 3);
+        for (col, row) in [(0, 0), (0, 1), (1, 1), (0, 2), (2, 2)] {
+            game.play(col, row);
+        }
+        let mut score = Score::default();
+        score.record(Outcome::Won(Player::X));
+
+        let screen = render(&game, Some((1, 0)), &score, "Player X wins");
+        let winning_x = ContentStyle::new()
+            .with(Color::Cyan)
+            .bold()
+            .on(Color::DarkGreen)
+            .apply(" X ")
+            .to_string();
+
+        assert_eq!(screen.matches(&winning_x).count(), 3);
+        assert!(
+            screen.contains(
+                &ContentStyle::new()
+                    .dark_grey()
+                    .reverse()
+                    .apply(" · ")
+                    .to_string()
+            )
+        );
+        assert!(screen.contains("X 1 : 0 O, draws 0\r\nPlayer X wins\r\n"));
+    }
+}
@@ -23,3 +23,37 @@ pub fn min_length<T>(length: usize) -> impl Fn(&Vec<T>) -> bool {
 pub fn is_not_empty<T>(values: &Vec<T>) -> bool {
     !values.is_empty()
 }
+
+/// Records written as CSV text, fields with separators, quotes or line breaks are quoted
+pub fn csv_string(records: &[Vec<String>]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    for record in records {
+        writer.write_record(record).map_err(|e| e.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// Text for a JSON string literal, without the surrounding quotes
+pub fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if character.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", character as u32));
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Text for a Markdown table cell
+pub fn escape_markdown(value: &str) -> String {
+    value.replace('|', "\\|")
+}